@@ -1,11 +1,14 @@
+use crate::random::Rng;
+use std::fmt;
+use std::fmt::{Display, Formatter, Write};
 use std::str::FromStr;
 
 pub fn find_empty_seat(passes: Vec<BoardingPass>) -> Result<usize, &'static str> {
-    let mut map = [false; 843];
+    let mut map = [false; 1024];
     for p in passes {
         map[p.seat_id()] = true
     }
-    for i in 1..841 {
+    for i in 1..1023 {
         if map[i - 1] && !map[i] && map[i + 1] {
             return Ok(i);
         }
@@ -13,6 +16,33 @@ pub fn find_empty_seat(passes: Vec<BoardingPass>) -> Result<usize, &'static str>
     Err("No empty seat found")
 }
 
+/// I generate `count` boarding passes for a contiguous block of seats at a random spot on the plane,
+/// with one seat in the middle of the block left empty, in random order.
+pub fn random_input(rng: &mut Rng, count: usize) -> String {
+    assert!(
+        count >= 2,
+        "Need at least two passes to surround an empty seat"
+    );
+    // seven row bits and three column bits make IDs 0 to 1023, and the block spans `count + 1`
+    assert!(count <= 1022, "Only 1024 seat IDs to fit the passes in");
+    let first = 1 + rng.below(1023 - count);
+    let empty = first + 1 + rng.below(count - 1);
+    let mut ids = (first..=(first + count))
+        .filter(|&id| id != empty)
+        .collect::<Vec<_>>();
+    rng.shuffle(&mut ids);
+    ids.iter()
+        .map(|&id| {
+            BoardingPass {
+                row: id / 8,
+                col: id % 8,
+            }
+            .to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 #[derive(Eq, PartialEq)]
 pub struct BoardingPass {
     row: usize,
@@ -49,6 +79,18 @@ impl FromStr for BoardingPass {
     }
 }
 
+impl Display for BoardingPass {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for b in (0..7).rev() {
+            f.write_char(if self.row & (1 << b) == 0 { 'F' } else { 'B' })?;
+        }
+        for b in (0..3).rev() {
+            f.write_char(if self.col & (1 << b) == 0 { 'L' } else { 'R' })?;
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -72,4 +114,34 @@ mod test {
         assert_eq!(4, p.col);
         assert_eq!(820, p.seat_id());
     }
+
+    #[test]
+    fn test_display() {
+        for s in &["FBFBBFFRLR", "BFFFBBFRRR", "FFFBBBFRRR", "BBFFBBFRLL"] {
+            assert_eq!(*s, s.parse::<BoardingPass>().unwrap().to_string());
+        }
+    }
+
+    #[test]
+    fn test_random_input() {
+        let mut rng = Rng::new(5);
+        let input = random_input(&mut rng, 200);
+        let passes = input
+            .lines()
+            .map(|l| l.parse::<BoardingPass>().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(200, passes.len());
+        let min = passes.iter().map(|p| p.seat_id()).min().unwrap();
+        let max = passes.iter().map(|p| p.seat_id()).max().unwrap();
+        assert_eq!(200, max - min);
+        let empty = find_empty_seat(passes).unwrap();
+        assert!(empty > min && empty < max);
+        // the biggest block fills every ID but zero
+        let passes = random_input(&mut rng, 1022)
+            .lines()
+            .map(|l| l.parse::<BoardingPass>().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(1022, passes.len());
+        assert!(find_empty_seat(passes).is_ok());
+    }
 }
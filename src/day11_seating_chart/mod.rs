@@ -1,3 +1,9 @@
+use crate::geom::bounds::Bounds;
+use crate::geom::point::Point;
+use crate::geom::Coords;
+use crate::geom2d::Dir8;
+use crate::normalize::normalize;
+use crate::random::Rng;
 use crate::timed_block;
 use std::fmt;
 use std::fmt::{Display, Formatter, Write};
@@ -16,7 +22,23 @@ pub fn solve(input: &str) {
     );
 }
 
+/// I generate a random `width` x `height` seat map, with about three quarters of the locations
+/// being (empty) seats, like the real input.
+pub fn random_input(rng: &mut Rng, width: usize, height: usize) -> String {
+    let mut s = String::with_capacity((width + 1) * height);
+    for y in 0..height {
+        if y > 0 {
+            s.push('\n');
+        }
+        for _ in 0..width {
+            s.push(if rng.chance(0.75) { 'L' } else { '.' });
+        }
+    }
+    s
+}
+
 fn load_map(s: &str) -> Map {
+    let s = normalize(s).text;
     let lines: Vec<&str> = s.trim().split('\n').collect();
     let width = lines[0].len();
     let height = lines.len();
@@ -76,43 +98,20 @@ impl Map {
     }
 
     fn occupied_neighbor_count(&self, i: usize) -> usize {
-        let from_x = (i % self.width) as isize;
-        let from_y = (i / self.width) as isize;
-        let max_x = self.width as isize - 1;
-        let max_y = self.height as isize - 1;
-        let find_seat = |dx, dy| {
-            let mut x = from_x;
-            let mut y = from_y;
-            loop {
-                x += dx;
-                y += dy;
-                if x < 0 || x > max_x || y < 0 || y > max_y {
-                    return None;
-                }
-                match self.at(x, y) {
-                    Floor => {}
-                    Empty => return Some(Empty),
-                    Occupied => return Some(Occupied),
-                }
-            }
-        };
-        [
-            find_seat(-1, -1),
-            find_seat(-1, 0),
-            find_seat(-1, 1),
-            find_seat(0, -1),
-            // not "this"
-            find_seat(0, 1),
-            find_seat(1, -1),
-            find_seat(1, 0),
-            find_seat(1, 1),
-        ]
-        .iter()
-        .map(|it| match it {
-            Some(Occupied) => 1,
-            _ => 0,
-        })
-        .sum()
+        let from = Point::new([(i % self.width) as isize, (i / self.width) as isize]);
+        let bounds = Bounds::new(
+            Point::origin(),
+            Point::new([self.width as isize - 1, self.height as isize - 1]),
+        );
+        Dir8::all()
+            .filter_map(|d| {
+                from.ray(&d.delta())
+                    .take_while(|p| bounds.contains(p))
+                    .map(|p| self.at(p[0], p[1]))
+                    .find(|&l| *l != Floor)
+            })
+            .filter(|&l| *l == Occupied)
+            .count()
     }
 
     fn at(&self, x: isize, y: isize) -> &Loc {
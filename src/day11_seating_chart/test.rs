@@ -213,3 +213,24 @@ fn example_two_3() {
     );
     assert_eq!(0, m.occupied_neighbor_count(3 * 7 + 3));
 }
+
+#[test]
+fn test_random_input() {
+    let mut rng = Rng::new(11);
+    let input = random_input(&mut rng, 17, 12);
+    let m = load_map(&input);
+    assert_eq!(17, m.width);
+    assert_eq!(12, m.height);
+    assert_eq!(0, m.occupied_seat_count());
+    assert_eq!(input, m.to_string());
+    assert_eq!(input, random_input(&mut Rng::new(11), 17, 12));
+}
+
+#[test]
+fn test_load_map_crlf() {
+    let crlf = EXAMPLE_ONE.replace('\n', "\r\n");
+    assert_eq!(
+        load_map(EXAMPLE_ONE).to_string(),
+        load_map(&crlf).to_string()
+    );
+}
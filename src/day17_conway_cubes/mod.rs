@@ -1,8 +1,11 @@
-use crate::histogram::Histogram;
+use crate::geom::bounds::Bounds;
+use crate::geom::neighborhood::MooreNeighbors;
+use crate::geom::Coords;
+use crate::histogram::IntoHistogram;
+use crate::normalize::normalize;
 use crate::{timed_block, vector_type};
-use std::collections::{HashMap, HashSet};
+use std::collections::HashSet;
 use std::fmt::{self, Display, Formatter, Write};
-use std::ops::Add;
 
 #[cfg(test)]
 mod test;
@@ -24,7 +27,7 @@ struct Game {
 impl Game {
     fn new(input: &str) -> Game {
         let mut active = HashSet::new();
-        for (y, l) in input.lines().enumerate() {
+        for (y, l) in normalize(input).text.lines().enumerate() {
             for (x, c) in l.chars().enumerate() {
                 if c == '#' {
                     active.insert(Point::new(x as isize, y as isize, 0, 0));
@@ -38,25 +41,12 @@ impl Game {
     }
 
     fn cycle(&self) -> Game {
-        let mut active = HashSet::new();
-        let mut neighbor_hist = HashMap::new();
-        for p in &self.active {
-            let mut count = 0;
-            for n in p.neighbors() {
-                if self.active.contains(&n) {
-                    count += 1;
-                }
-                neighbor_hist.increment_bucket(n);
-            }
-            if count == 2 || count == 3 {
-                active.insert(p.clone());
-            }
-        }
-        for (p, nc) in neighbor_hist {
-            if nc == 3 && !self.active.contains(&p) {
-                active.insert(p.clone());
-            }
-        }
+        let neighbor_hist = self.active.iter().flat_map(|p| p.neighbors()).histogram();
+        let active = neighbor_hist
+            .into_iter()
+            .filter(|(p, nc)| *nc == 3 || (*nc == 2 && self.active.contains(p)))
+            .map(|(p, _)| p)
+            .collect();
         Game {
             active,
             cycle_count: self.cycle_count + 1,
@@ -70,12 +60,11 @@ impl Game {
 
 impl Display for Game {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        let (min, max) = self
-            .active
-            .iter()
-            .fold((Point::origin(), Point::origin()), |(min, max), p| {
-                (min.rectilinear_min(p), max.rectilinear_max(p))
-            });
+        let bounds = match Bounds::from_points(self.active.iter().copied()) {
+            Some(b) => b,
+            None => return Ok(()),
+        };
+        let (min, max) = (bounds.min(), bounds.max());
         for w in min.w..=max.w {
             for z in min.z..=max.z {
                 writeln!(f, "z={}, w={}", z, w)?;
@@ -97,79 +86,9 @@ impl Display for Game {
 }
 
 vector_type![Point, isize, x, y, z, w];
-type Offset = [isize; 4];
 
 impl Point {
-    pub fn neighbors(&self) -> Neighbors {
-        Neighbors::new(self)
-    }
-}
-
-impl Add<&Offset> for &Point {
-    type Output = Point;
-
-    fn add(self, rhs: &Offset) -> Self::Output {
-        Point::new(
-            self.x + rhs[0],
-            self.y + rhs[1],
-            self.z + rhs[2],
-            self.w + rhs[3],
-        )
-    }
-}
-
-#[derive(Default)]
-struct NeighborOffsets {
-    o: Offset,
-}
-
-impl Iterator for NeighborOffsets {
-    type Item = Offset;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        for i in 0..self.o.len() {
-            match self.o[i] {
-                0 => {
-                    for j in 0..i {
-                        self.o[j] = 0;
-                    }
-                    self.o[i] = -1;
-                }
-                -1 => {
-                    for j in 0..i {
-                        self.o[j] = 0;
-                    }
-                    self.o[i] = 1;
-                }
-                _ => continue,
-            }
-            return Some(self.o.clone());
-        }
-        None
-    }
-}
-
-struct Neighbors<'a> {
-    p: &'a Point,
-    os: NeighborOffsets,
-}
-
-impl<'a> Neighbors<'a> {
-    fn new(p: &'a Point) -> Neighbors {
-        Neighbors {
-            p,
-            os: Default::default(),
-        }
-    }
-}
-
-impl Iterator for Neighbors<'_> {
-    type Item = Point;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        match self.os.next() {
-            Some(o) => Some(self.p + &o),
-            None => None,
-        }
+    pub fn neighbors(&self) -> MooreNeighbors<Point, 4> {
+        self.moore_neighbors(1)
     }
 }
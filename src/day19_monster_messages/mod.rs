@@ -1,3 +1,5 @@
+use crate::normalize::normalize;
+use crate::random::Rng;
 use regex::Regex;
 use std::cell::RefCell;
 use std::collections::HashMap;
@@ -17,7 +19,8 @@ fn parse(input: &str) -> (Vec<&str>, Vec<&str>) {
 }
 
 fn part_one(input: &str) -> usize {
-    let (rule_list, strings) = parse(input);
+    let input = normalize(input).text;
+    let (rule_list, strings) = parse(&input);
     let mut rule = Flattener::new(&rule_list).flattened();
     rule.insert(0, '^');
     rule.push('$');
@@ -26,7 +29,8 @@ fn part_one(input: &str) -> usize {
 }
 
 fn both_parts(input: &str) -> (usize, usize) {
-    let (rule_list, strings) = parse(input);
+    let input = normalize(input).text;
+    let (rule_list, strings) = parse(&input);
     let flattener = Flattener::new(&rule_list);
     /*
     0: 8 11
@@ -81,6 +85,121 @@ fn both_parts(input: &str) -> (usize, usize) {
     (one, two)
 }
 
+enum RandomRule {
+    Char(char),
+    Alternatives(Vec<Vec<usize>>),
+}
+
+impl RandomRule {
+    fn expand(&self, rules: &[RandomRule], rng: &mut Rng, message: &mut String) {
+        match self {
+            RandomRule::Char(c) => message.push(*c),
+            RandomRule::Alternatives(alts) => {
+                for &r in rng.pick(alts) {
+                    rules[r].expand(rules, rng, message);
+                }
+            }
+        }
+    }
+}
+
+/// I push `depth` layers of random, loop-free rules over the `"a"` and `"b"` terminals, and then a
+/// rule matching `first` followed by something from the top layer, returning that rule's index.
+/// Every sequence in a layer is the same length, so each rule only matches messages of one length.
+fn random_sub_grammar(
+    rules: &mut Vec<RandomRule>,
+    rng: &mut Rng,
+    depth: usize,
+    first: usize,
+) -> usize {
+    let mut prev_layer = 0..2;
+    for _ in 0..depth {
+        let start = rules.len();
+        let seq_len = 1 + rng.below(2);
+        for _ in 0..(2 + rng.below(3)) {
+            let alts = (0..(1 + rng.below(2)))
+                .map(|_| {
+                    (0..seq_len)
+                        .map(|_| prev_layer.start + rng.below(prev_layer.len()))
+                        .collect()
+                })
+                .collect();
+            rules.push(RandomRule::Alternatives(alts));
+        }
+        prev_layer = start..rules.len();
+    }
+    let top = prev_layer.start + rng.below(prev_layer.len());
+    rules.push(RandomRule::Alternatives(vec![vec![first, top]]));
+    rules.len() - 1
+}
+
+/// I generate a random grammar shaped like the puzzle's, with `0: 8 11`, `8: 42`, and `11: 42 31`,
+/// and `depth` layers of random rules under each of 42 and 31. As part two relies on, everything 42
+/// matches is the same length and starts with `"a"`, and everything 31 matches is the same length
+/// and starts with `"b"`. Then come `count` messages of some 42s followed by fewer 31s, about half
+/// of them with a single character flipped (which usually won't match).
+pub fn random_input(rng: &mut Rng, depth: usize, count: usize) -> String {
+    let mut rules = vec![RandomRule::Char('a'), RandomRule::Char('b')];
+    let r42 = random_sub_grammar(&mut rules, rng, depth, 0);
+    let r31 = random_sub_grammar(&mut rules, rng, depth, 1);
+    let r8 = rules.len();
+    rules.push(RandomRule::Alternatives(vec![vec![r42]]));
+    rules.push(RandomRule::Alternatives(vec![vec![r42, r31]]));
+    rules.push(RandomRule::Alternatives(vec![vec![r8, r8 + 1]]));
+    // the structural rules keep their numbers, and the rest get shuffled ones
+    let fixed = [(r42, 42), (r31, 31), (r8, 8), (r8 + 1, 11), (r8 + 2, 0)];
+    let mut free = (1..)
+        .filter(|n| fixed.iter().all(|&(_, f)| f != *n))
+        .take(rules.len() - fixed.len())
+        .collect::<Vec<_>>();
+    rng.shuffle(&mut free);
+    let numbers = (0..rules.len())
+        .map(|i| match fixed.iter().find(|&&(r, _)| r == i) {
+            Some(&(_, n)) => n,
+            None => free.pop().unwrap(),
+        })
+        .collect::<Vec<_>>();
+    let mut lines = rules
+        .iter()
+        .zip(&numbers)
+        .map(|(r, n)| match r {
+            RandomRule::Char(c) => format!("{}: \"{}\"", n, c),
+            RandomRule::Alternatives(alts) => format!(
+                "{}: {}",
+                n,
+                alts.iter()
+                    .map(|seq| {
+                        seq.iter()
+                            .map(|&r| numbers[r].to_string())
+                            .collect::<Vec<_>>()
+                            .join(" ")
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" | ")
+            ),
+        })
+        .collect::<Vec<_>>();
+    rng.shuffle(&mut lines);
+    lines.push(String::new());
+    for _ in 0..count {
+        let mut message = String::new();
+        let n31 = 1 + rng.below(2);
+        for _ in 0..(n31 + 1 + rng.below(2)) {
+            rules[r42].expand(&rules, rng, &mut message);
+        }
+        for _ in 0..n31 {
+            rules[r31].expand(&rules, rng, &mut message);
+        }
+        if rng.chance(0.5) {
+            let i = rng.below(message.len());
+            let c = if &message[i..=i] == "a" { "b" } else { "a" };
+            message.replace_range(i..=i, c);
+        }
+        lines.push(message);
+    }
+    lines.join("\n")
+}
+
 struct Flattener<'a> {
     unparsed: HashMap<&'a str, &'a str>,
     parsed: RefCell<HashMap<&'a str, String>>,
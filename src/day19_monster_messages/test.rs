@@ -84,3 +84,18 @@ fn example_two() {
 fn example_three() {
     assert_eq!((3, 12), both_parts(EXAMPLE_THREE));
 }
+
+#[test]
+fn test_random_input() {
+    let mut rng = Rng::new(19);
+    for depth in 0..5 {
+        let input = random_input(&mut rng, depth, 40);
+        let (rules, messages) = parse(&input);
+        assert_eq!(40, messages.len());
+        assert!(rules.iter().any(|r| r == &"0: 8 11"));
+        let (one, two) = both_parts(&input);
+        assert_eq!(part_one(&input), one);
+        assert!(one <= two && two <= 40);
+        assert!(two > 0);
+    }
+}
@@ -1,3 +1,5 @@
+use crate::normalize::normalize;
+use crate::random::Rng;
 use crate::timed_block;
 use std::collections::VecDeque;
 use std::fmt;
@@ -29,6 +31,26 @@ fn part_two(input: &str) -> usize {
     a * b
 }
 
+/// I generate a random labelling of `size` cups, suitable for seeding a game of any size.
+pub fn random_seed(rng: &mut Rng, size: usize) -> Vec<usize> {
+    let mut seed = (1..=size).collect::<Vec<_>>();
+    rng.shuffle(&mut seed);
+    seed
+}
+
+/// I generate a random puzzle input of `size` cups. Since each label is a single digit, at most
+/// nine cups can be generated this way; use `random_seed` for bigger games.
+pub fn random_input(rng: &mut Rng, size: usize) -> String {
+    assert!(
+        (1..=9).contains(&size),
+        "Only 1-9 cups can be written as input"
+    );
+    random_seed(rng, size)
+        .iter()
+        .map(|d| d.to_string())
+        .collect()
+}
+
 struct Cups {
     head: usize,
     tail: usize,
@@ -123,8 +145,9 @@ impl<'a> Iterator for Labels<'a> {
 
 impl From<&str> for Cups {
     fn from(s: &str) -> Self {
+        let s = normalize(s).text;
         let mut seed = VecDeque::with_capacity(s.len());
-        let mut n = s.parse::<usize>().unwrap();
+        let mut n = s.trim().parse::<usize>().unwrap();
         while n > 0 {
             seed.push_front(n % 10);
             n /= 10;
@@ -80,3 +80,22 @@ fn example_one_part_one() {
 fn example_one_part_two() {
     assert_eq!(149245887792, part_two(&EXAMPLE_ONE));
 }
+
+#[test]
+fn test_random_input() {
+    let mut rng = Rng::new(23);
+    for size in 1..=9 {
+        let cups = Cups::from(random_input(&mut rng, size).as_str());
+        let mut labels = cups.one_first();
+        labels.sort();
+        assert_eq!((1..=size).collect::<Vec<_>>(), labels);
+    }
+    let mut cups = Cups::new(&random_seed(&mut rng, 50));
+    assert_eq!(50, cups.size);
+    for _ in 0..100 {
+        cups.tick();
+    }
+    let mut labels = cups.one_first();
+    labels.sort();
+    assert_eq!((1..=50).collect::<Vec<_>>(), labels);
+}
@@ -1,6 +1,8 @@
-use crate::histogram::Histogram;
+use crate::histogram::IntoHistogram;
+use crate::normalize::normalize;
+use crate::random::Rng;
 use crate::timed_block;
-use std::collections::{HashMap, HashSet};
+use std::collections::HashSet;
 use std::fmt::{self, Display, Formatter};
 use Dir::*;
 
@@ -17,7 +19,7 @@ type Layout = HashSet<Tile>;
 
 fn initial_layout(input: &str) -> Layout {
     let mut black_tiles = HashSet::new();
-    for path in input.lines().map(&parse_path) {
+    for path in normalize(input).text.lines().map(&parse_path) {
         let t = Tile::origin().walk(&path);
         if black_tiles.contains(&t) {
             black_tiles.remove(&t);
@@ -37,27 +39,27 @@ fn part_two(layout: &Layout) -> usize {
 }
 
 fn do_step(layout: &Layout) -> Layout {
-    let mut next = HashSet::new();
-    let mut black_neighbor_hist = HashMap::new();
-    for tile in layout {
-        let mut count = 0;
-        for neighbor in tile.neighbors() {
-            if layout.contains(&neighbor) {
-                count += 1;
-            }
-            black_neighbor_hist.increment_bucket(neighbor);
+    let black_neighbor_hist = layout.iter().flat_map(|t| t.neighbors()).histogram();
+    black_neighbor_hist
+        .into_iter()
+        .filter(|(t, nc)| *nc == 2 || (*nc == 1 && layout.contains(t)))
+        .map(|(t, _)| t)
+        .collect()
+}
+
+/// I generate `count` random tile paths, each between one and `max_len` steps long.
+pub fn random_input(rng: &mut Rng, count: usize, max_len: usize) -> String {
+    const STEPS: [&str; 6] = ["nw", "ne", "e", "se", "sw", "w"];
+    let mut s = String::new();
+    for i in 0..count {
+        if i > 0 {
+            s.push('\n');
         }
-        if count == 1 || count == 2 {
-            next.insert(tile.clone());
+        for _ in 0..=rng.below(max_len) {
+            s.push_str(STEPS[rng.below(STEPS.len())]);
         }
     }
-    for (t, _) in black_neighbor_hist
-        .iter()
-        .filter(|(t, &nc)| nc == 2 && !layout.contains(t))
-    {
-        next.insert(t.clone());
-    }
-    next
+    s
 }
 
 fn parse_path(s: &str) -> Vec<Dir> {
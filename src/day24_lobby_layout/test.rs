@@ -141,3 +141,13 @@ fn example_four_part_two() {
     let layout = initial_layout(&EXAMPLE_FOUR);
     assert_eq!(2208, part_two(&layout));
 }
+
+#[test]
+fn test_random_input() {
+    let mut rng = Rng::new(24);
+    let input = random_input(&mut rng, 50, 20);
+    let paths = input.lines().map(parse_path).collect::<Vec<_>>();
+    assert_eq!(50, paths.len());
+    assert!(paths.iter().all(|p| !p.is_empty() && p.len() <= 20));
+    assert!(initial_layout(&input).len() <= 50);
+}
@@ -1,3 +1,4 @@
+use crate::normalize::normalize;
 use crate::timed_block;
 
 #[cfg(test)]
@@ -10,7 +11,8 @@ pub fn solve(input: &str) {
 const MODULUS: usize = 20201227;
 
 fn part_one(input: &str) -> usize {
-    let public_keys = input
+    let public_keys = normalize(input)
+        .text
         .trim()
         .lines()
         .map(|l| l.trim().parse::<usize>().unwrap())
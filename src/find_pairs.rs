@@ -1,34 +1,521 @@
-use std::ops::Add;
+use crate::histogram::Histogram;
+use num_traits::{CheckedSub, PrimInt, Zero};
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::{Add, Range};
+
+/// The ways a `PairFinder` can go about its search. They'll all find a pair if one exists, but if
+/// there are several, they may not find the same one.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Strategy {
+    /// Pick a strategy based on the size of the input: nested loops for short ones, and a hash set
+    /// for the rest.
+    Auto,
+    /// Check every pair with nested loops. O(n^2), but no allocation, so fast for small inputs.
+    Nested,
+    /// Look up each element's complement in a hash set of the elements before it. O(n).
+    Hashed,
+    /// Sort (a copy of) the elements and scan inward from both ends. O(n log n).
+    Sorted,
+}
+
+/// Inputs shorter than this are searched with nested loops, longer ones w/ a hash set (or by
+/// sorting, if the elements can't be hashed).
+const AUTO_THRESHOLD: usize = 64;
 
 pub trait PairFinder<T>
 where
     T: Add<Output = T> + Eq,
 {
+    /// Find a pair of elements which sum to `sum`, with nested loops for short inputs and by sorting
+    /// for longer ones. This only needs the elements to be ordered, but it adds them up, so every
+    /// pair it tries must sum to something `T` can hold. `find_pair_with_sum_using` doesn't add, so
+    /// it's safe near `T`'s limits.
     fn find_pair_with_sum(&self, sum: T) -> Option<(T, T)>;
+
+    /// Find a pair of elements which sum to `sum`, using the given strategy. The pair is returned
+    /// in the order the elements appear.
+    fn find_pair_with_sum_using(&self, sum: T, strategy: Strategy) -> Option<(T, T)>
+    where
+        T: CheckedSub + Hash;
+
+    /// Iterate over every index pair `(i, j)`, with `i < j`, whose elements sum to `sum`.
+    fn pairs_with_sum(&self, sum: T) -> PairsWithSum<'_, T>
+    where
+        T: CheckedSub;
+
+    /// Iterate over every index pair `(i, j)`, with `i < j`, whose elements satisfy `predicate`.
+    fn pairs_matching<P>(&self, predicate: P) -> Pairs<'_, T, P>
+    where
+        P: FnMut(&T, &T) -> bool;
+
+    /// Count the index pairs whose elements sum to `sum`. Duplicate values each count, so `[2, 2,
+    /// 2]` has three pairs which sum to four.
+    fn count_pairs_with_sum(&self, sum: T) -> usize
+    where
+        T: CheckedSub + Hash;
 }
 
 impl<T> PairFinder<T> for [T]
 where
-    T: Add<Output = T> + Ord + Eq + Copy,
+    T: Add<Output = T> + Ord + Copy,
 {
     fn find_pair_with_sum(&self, sum: T) -> Option<(T, T)> {
-        for (i, &a) in self.iter().enumerate() {
-            if a >= sum {
-                continue;
+        let cmp = |a: T, b: T| (a + b).cmp(&sum);
+        if self.len() < AUTO_THRESHOLD {
+            self.find_pair_nested(cmp)
+        } else {
+            self.find_pair_sorted(cmp)
+        }
+    }
+
+    fn find_pair_with_sum_using(&self, sum: T, strategy: Strategy) -> Option<(T, T)>
+    where
+        T: CheckedSub + Hash,
+    {
+        let cmp = |a: T, b: T| cmp_pair_sum(a, b, sum);
+        match strategy {
+            Strategy::Auto if self.len() < AUTO_THRESHOLD => self.find_pair_nested(cmp),
+            Strategy::Auto => self.find_pair_hashed(sum),
+            Strategy::Nested => self.find_pair_nested(cmp),
+            Strategy::Hashed => self.find_pair_hashed(sum),
+            Strategy::Sorted => self.find_pair_sorted(cmp),
+        }
+    }
+
+    fn pairs_with_sum(&self, sum: T) -> PairsWithSum<'_, T>
+    where
+        T: CheckedSub,
+    {
+        PairsWithSum {
+            scan: PairScan::new(self),
+            sum,
+        }
+    }
+
+    fn pairs_matching<P>(&self, predicate: P) -> Pairs<'_, T, P>
+    where
+        P: FnMut(&T, &T) -> bool,
+    {
+        Pairs {
+            scan: PairScan::new(self),
+            predicate,
+        }
+    }
+
+    fn count_pairs_with_sum(&self, sum: T) -> usize
+    where
+        T: CheckedSub + Hash,
+    {
+        let mut seen = HashMap::with_capacity(self.len());
+        let mut count = 0;
+        for &b in self {
+            if let Some(a) = sum.checked_sub(&b) {
+                count += seen.get_bucket(&a);
             }
+            seen.increment_bucket(b);
+        }
+        count
+    }
+}
+
+/// I am the position of a scan over a slice's index pairs, in order of the first index, then the
+/// second, shared by the pair iterators.
+struct PairScan<'a, T> {
+    items: &'a [T],
+    i: usize,
+    j: usize,
+}
+
+impl<'a, T> PairScan<'a, T> {
+    fn new(items: &'a [T]) -> PairScan<'a, T> {
+        PairScan { items, i: 0, j: 1 }
+    }
+
+    /// Advance to the next index pair whose elements satisfy `predicate`.
+    fn next_where<P>(&mut self, mut predicate: P) -> Option<(usize, usize)>
+    where
+        P: FnMut(&T, &T) -> bool,
+    {
+        while self.i < self.items.len() {
+            while self.j < self.items.len() {
+                let j = self.j;
+                self.j += 1;
+                if predicate(&self.items[self.i], &self.items[j]) {
+                    return Some((self.i, j));
+                }
+            }
+            self.i += 1;
+            self.j = self.i + 1;
+        }
+        None
+    }
+}
+
+/// I iterate over the index pairs of a slice whose elements satisfy a predicate, in order of the
+/// first index, then the second.
+pub struct Pairs<'a, T, P> {
+    scan: PairScan<'a, T>,
+    predicate: P,
+}
+
+impl<T, P> Iterator for Pairs<'_, T, P>
+where
+    P: FnMut(&T, &T) -> bool,
+{
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.scan.next_where(&mut self.predicate)
+    }
+}
+
+/// I iterate over the index pairs of a slice whose elements sum to a target, in the same order as
+/// `Pairs`. I compare each element with the target less its partner, so nothing can overflow.
+pub struct PairsWithSum<'a, T> {
+    scan: PairScan<'a, T>,
+    sum: T,
+}
+
+impl<T> Iterator for PairsWithSum<'_, T>
+where
+    T: CheckedSub + Eq,
+{
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let sum = &self.sum;
+        self.scan
+            .next_where(|a, b| sum.checked_sub(b).as_ref() == Some(a))
+    }
+}
+
+/// The searches behind the strategies. Those which don't need hashing take `cmp(a, b)`, which
+/// compares `a + b` with the target however the caller can.
+trait PairStrategies<T> {
+    fn find_pair_nested<F>(&self, cmp: F) -> Option<(T, T)>
+    where
+        F: FnMut(T, T) -> Ordering;
+    fn find_pair_hashed(&self, sum: T) -> Option<(T, T)>
+    where
+        T: CheckedSub + Hash;
+    fn find_pair_sorted<F>(&self, cmp: F) -> Option<(T, T)>
+    where
+        F: FnMut(T, T) -> Ordering;
+}
+
+impl<T> PairStrategies<T> for [T]
+where
+    T: Ord + Copy,
+{
+    fn find_pair_nested<F>(&self, mut cmp: F) -> Option<(T, T)>
+    where
+        F: FnMut(T, T) -> Ordering,
+    {
+        for (i, &a) in self.iter().enumerate() {
             for &b in self.iter().skip(i + 1) {
-                if a + b == sum {
+                if cmp(a, b) == Ordering::Equal {
+                    return Some((a, b));
+                }
+            }
+        }
+        None
+    }
+
+    fn find_pair_hashed(&self, sum: T) -> Option<(T, T)>
+    where
+        T: CheckedSub + Hash,
+    {
+        let mut seen = HashSet::with_capacity(self.len());
+        for &b in self {
+            // an unsigned complement can't be negative
+            if let Some(a) = sum.checked_sub(&b) {
+                if seen.contains(&a) {
                     return Some((a, b));
                 }
             }
+            seen.insert(b);
+        }
+        None
+    }
+
+    fn find_pair_sorted<F>(&self, mut cmp: F) -> Option<(T, T)>
+    where
+        F: FnMut(T, T) -> Ordering,
+    {
+        if self.len() < 2 {
+            return None;
+        }
+        let mut order = (0..self.len()).collect::<Vec<_>>();
+        order.sort_by_key(|&i| self[i]);
+        let (mut lo, mut hi) = (0, order.len() - 1);
+        while lo < hi {
+            match cmp(self[order[lo]], self[order[hi]]) {
+                Ordering::Less => lo += 1,
+                Ordering::Greater => hi -= 1,
+                Ordering::Equal => {
+                    let (i, j) = (order[lo].min(order[hi]), order[lo].max(order[hi]));
+                    return Some((self[i], self[j]));
+                }
+            }
         }
         None
     }
 }
 
+/// I find `k` distinct elements (by position, not necessarily by value) of a collection which sum
+/// to a target. The search sorts a copy of the values and then fixes `k - 2` elements before doing
+/// a two-pointer scan for the last pair, so it's O(n^(k-1)) rather than O(n^k). Sorting means I
+/// don't need `Hash`, but I do need `CheckedSub` and `Zero` on top of `Add`: rather than adding up
+/// chosen elements, I subtract them from the target with overflow checks, so I can't overflow, and
+/// negative values and unsigned types near their limits work alike.
+///
+/// # Examples
+///
+/// ```
+/// use aoc_2020::find_pairs::KSumFinder;
+///
+/// let nums = vec![1721, 979, 366, 299, 675, 1456];
+/// assert_eq!(Some(vec![979, 366, 675]), nums.find_k_with_sum(3, 2020));
+/// assert_eq!(Some(vec![1, 2, 4]), nums.find_k_indices_with_sum(3, 2020));
+/// assert_eq!(None, nums.find_k_with_sum(4, 2020));
+/// assert_eq!(None, [200u8, 100].find_k_with_sum(2, 50));
+/// assert_eq!(Some(vec![-128, 1, 127]), [-128i8, 1, 127].find_k_with_sum(3, 0));
+/// ```
+pub trait KSumFinder<T>
+where
+    T: Add<Output = T> + CheckedSub + Zero + Ord + Copy,
+{
+    /// Find the indices, in ascending order, of `k` elements which sum to `sum`.
+    fn find_k_indices_with_sum(&self, k: usize, sum: T) -> Option<Vec<usize>>;
+
+    /// Find the values of `k` elements which sum to `sum`, in the order they appear.
+    fn find_k_with_sum(&self, k: usize, sum: T) -> Option<Vec<T>>;
+}
+
+impl<T> KSumFinder<T> for [T]
+where
+    T: Add<Output = T> + CheckedSub + Zero + Ord + Copy,
+{
+    fn find_k_indices_with_sum(&self, k: usize, sum: T) -> Option<Vec<usize>> {
+        if k == 0 || k > self.len() {
+            return None;
+        }
+        let mut order = (0..self.len()).collect::<Vec<_>>();
+        order.sort_by_key(|&i| self[i]);
+        let sorted = order.iter().map(|&i| self[i]).collect::<Vec<_>>();
+        let mut chosen = Vec::with_capacity(k);
+        if !find_k_sorted(&sorted, 0..sorted.len(), k, sum, &mut chosen) {
+            return None;
+        }
+        let mut indices = chosen.iter().map(|&i| order[i]).collect::<Vec<_>>();
+        indices.sort_unstable();
+        Some(indices)
+    }
+
+    fn find_k_with_sum(&self, k: usize, sum: T) -> Option<Vec<T>> {
+        self.find_k_indices_with_sum(k, sum)
+            .map(|indices| indices.iter().map(|&i| self[i]).collect())
+    }
+}
+
+/// I compare `a + b` with `sum` without overflowing, by comparing `a` with `sum - b` instead. If
+/// that doesn't fit in `T`, it's either below everything (when `b > sum`) or above everything.
+fn cmp_pair_sum<T>(a: T, b: T, sum: T) -> Ordering
+where
+    T: CheckedSub + Ord,
+{
+    match sum.checked_sub(&b) {
+        Some(rest) => a.cmp(&rest),
+        None => b.cmp(&sum),
+    }
+}
+
+/// I search `sorted[within]` for `k` elements which sum to `sum`, pushing their indices onto
+/// `chosen` if found. Each element I fix is subtracted from the target: the largest remaining one
+/// when the target isn't negative, and the smallest when it is. That way a subtraction only
+/// overflows when no set including that element could reach the target.
+fn find_k_sorted<T>(
+    sorted: &[T],
+    within: Range<usize>,
+    k: usize,
+    sum: T,
+    chosen: &mut Vec<usize>,
+) -> bool
+where
+    T: CheckedSub + Zero + Ord + Copy,
+{
+    let Range { start, end } = within;
+    match k {
+        1 => match sorted[start..end].binary_search(&sum) {
+            Ok(i) => {
+                chosen.push(start + i);
+                true
+            }
+            Err(_) => false,
+        },
+        2 => {
+            let (mut lo, mut hi) = (start, end - 1);
+            while lo < hi {
+                match cmp_pair_sum(sorted[lo], sorted[hi], sum) {
+                    Ordering::Less => lo += 1,
+                    Ordering::Greater => hi -= 1,
+                    Ordering::Equal => {
+                        chosen.push(lo);
+                        chosen.push(hi);
+                        return true;
+                    }
+                }
+            }
+            false
+        }
+        // if `sum - v` overflows for the largest element `v`, then either `v` is bigger than `sum`
+        // and nothing is negative, or `v` is negative and so is everything else
+        _ if sum >= T::zero() => {
+            for j in ((start + k - 1)..end).rev() {
+                if j + 1 < end && sorted[j] == sorted[j + 1] {
+                    // already tried this value in this position
+                    continue;
+                }
+                if let Some(rest) = sum.checked_sub(&sorted[j]) {
+                    chosen.push(j);
+                    if find_k_sorted(sorted, start..j, k - 1, rest, chosen) {
+                        return true;
+                    }
+                    chosen.pop();
+                }
+            }
+            false
+        }
+        // if `sum - v` overflows for the smallest element `v`, then it and everything else is
+        // positive
+        _ => {
+            for i in start..=(end - k) {
+                if i > start && sorted[i] == sorted[i - 1] {
+                    continue;
+                }
+                if let Some(rest) = sum.checked_sub(&sorted[i]) {
+                    chosen.push(i);
+                    if find_k_sorted(sorted, (i + 1)..end, k - 1, rest, chosen) {
+                        return true;
+                    }
+                    chosen.pop();
+                }
+            }
+            false
+        }
+    }
+}
+
+/// I incrementally validate a stream of numbers, checking that each is the sum of two of the
+/// `size` numbers before it. The first `size` numbers are the preamble, and are always valid. The
+/// window slides along one number at a time, and each check is a `PairFinder` search of it, which
+/// for windows of typical size is a nested loop with no allocation.
+///
+/// # Examples
+///
+/// ```
+/// use aoc_2020::find_pairs::WindowValidator;
+///
+/// let mut v = WindowValidator::new(3);
+/// assert!(v.push(1));
+/// assert!(v.push(2));
+/// assert!(v.push(3));
+/// assert!(v.push(5)); // 2 + 3
+/// assert!(!v.push(4)); // 1 isn't in the window any more
+/// assert!(v.push(8)); // 3 + 5, even though 4 was invalid
+/// ```
+pub struct WindowValidator<T> {
+    size: usize,
+    window: VecDeque<T>,
+}
+
+impl<T> WindowValidator<T>
+where
+    T: Add<Output = T> + CheckedSub + Ord + Hash + Copy,
+{
+    pub fn new(size: usize) -> WindowValidator<T> {
+        WindowValidator {
+            size,
+            window: VecDeque::with_capacity(size + 1),
+        }
+    }
+
+    /// Check whether `n` is valid, and then slide it into the window.
+    pub fn push(&mut self, n: T) -> bool {
+        let valid = self.window.len() < self.size
+            || self
+                .window
+                .make_contiguous()
+                .find_pair_with_sum_using(n, Strategy::Auto)
+                .is_some();
+        self.window.push_back(n);
+        if self.window.len() > self.size {
+            self.window.pop_front();
+        }
+        valid
+    }
+}
+
+/// I find the index of the first number which isn't the sum of two of the `size` numbers before it.
+pub fn find_first_invalid<T>(nums: &[T], size: usize) -> Option<usize>
+where
+    T: Add<Output = T> + CheckedSub + Ord + Hash + Copy,
+{
+    let mut validator = WindowValidator::new(size);
+    nums.iter().position(|&n| !validator.push(n))
+}
+
+/// I find the first (by end) contiguous range of at least `min_len` numbers which sums to `sum`.
+/// A running total and a hash map of totals seen so far makes this linear time, and unlike a
+/// two-pointer scan it's correct even when there are negative numbers. The total is kept as an
+/// `i128` so it can't overflow for any narrower type; for `i128` and `u128`, a number or total
+/// which doesn't fit in an `i128` panics.
+///
+/// # Examples
+///
+/// ```
+/// use aoc_2020::find_pairs::find_contiguous_with_sum;
+///
+/// let nums = [3, -2, 5, 1, 4];
+/// assert_eq!(Some(1..4), find_contiguous_with_sum(&nums, 4, 2));
+/// assert_eq!(Some(0..3), find_contiguous_with_sum(&nums, 6, 2));
+/// assert_eq!(Some(1..2), find_contiguous_with_sum(&nums[3..], 4, 1));
+/// assert_eq!(None, find_contiguous_with_sum(&nums, 100, 1));
+/// assert_eq!(Some(1..3), find_contiguous_with_sum(&[200u8, 100, 50], 150, 1));
+/// ```
+pub fn find_contiguous_with_sum<T>(nums: &[T], sum: T, min_len: usize) -> Option<Range<usize>>
+where
+    T: PrimInt,
+{
+    let wide = |n: T| n.to_i128().expect("Numbers must fit in an i128");
+    let min_len = min_len.max(1);
+    let sum = wide(sum);
+    // the earliest index at which each running total was seen, which yields the longest range
+    let mut starts = HashMap::new();
+    let mut total = 0i128;
+    starts.insert(total, 0);
+    for (i, &n) in nums.iter().enumerate() {
+        let end = i + 1;
+        total = total
+            .checked_add(wide(n))
+            .expect("Running total must fit in an i128");
+        // a total too far from `sum` can't match any total seen so far
+        if let Some(&start) = total.checked_sub(sum).and_then(|t| starts.get(&t)) {
+            if end - start >= min_len {
+                return Some(start..end);
+            }
+        }
+        starts.entry(total).or_insert(end);
+    }
+    None
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::random::Rng;
 
     #[test]
     fn from_day_one() {
@@ -39,4 +526,287 @@ mod test {
         assert_eq!(Some((1721, 299)), nums[..5].find_pair_with_sum(2020));
         assert_eq!(None, nums[1..].find_pair_with_sum(2020));
     }
+
+    const STRATEGIES: [Strategy; 4] = [
+        Strategy::Auto,
+        Strategy::Nested,
+        Strategy::Hashed,
+        Strategy::Sorted,
+    ];
+
+    #[test]
+    fn strategies_from_day_one() {
+        let nums = vec![1721, 979, 366, 299, 675, 1456];
+        for &s in &STRATEGIES {
+            assert_eq!(Some((1721, 299)), nums.find_pair_with_sum_using(2020, s));
+            assert_eq!(None, nums[1..].find_pair_with_sum_using(2020, s));
+            assert_eq!(None, nums[..1].find_pair_with_sum_using(1721, s));
+            assert_eq!(None, nums[..0].find_pair_with_sum_using(0, s));
+        }
+    }
+
+    #[test]
+    fn strategies_with_negatives() {
+        let nums = [5, -1, 12, -20];
+        for &s in &STRATEGIES {
+            assert_eq!(Some((5, -1)), nums.find_pair_with_sum_using(4, s));
+            assert_eq!(Some((-1, -20)), nums.find_pair_with_sum_using(-21, s));
+            assert_eq!(Some((12, -20)), nums.find_pair_with_sum_using(-8, s));
+            assert_eq!(None, nums.find_pair_with_sum_using(0, s));
+        }
+    }
+
+    #[test]
+    fn strategies_with_duplicates() {
+        let nums = [5u32, 3, 5];
+        for &s in &STRATEGIES {
+            assert_eq!(Some((5, 5)), nums.find_pair_with_sum_using(10, s));
+            // either 5 pairs with the 3
+            let pair = nums.find_pair_with_sum_using(8, s);
+            assert!(pair == Some((5, 3)) || pair == Some((3, 5)));
+            assert_eq!(None, nums.find_pair_with_sum_using(6, s));
+            assert_eq!(None, nums.find_pair_with_sum_using(1, s));
+        }
+    }
+
+    /// Ordered and addable, but neither hashable nor checked.
+    #[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+    struct Cents(u32);
+
+    impl Add for Cents {
+        type Output = Cents;
+
+        fn add(self, other: Cents) -> Cents {
+            Cents(self.0 + other.0)
+        }
+    }
+
+    #[test]
+    fn only_ordered() {
+        let prices = [Cents(250), Cents(99), Cents(1), Cents(150)];
+        assert_eq!(
+            Some((Cents(250), Cents(150))),
+            prices.find_pair_with_sum(Cents(400))
+        );
+        assert_eq!(None, prices.find_pair_with_sum(Cents(2)));
+        // long enough to be sorted rather than looped over
+        let prices = (0..100).map(|c| Cents(c * 2)).collect::<Vec<_>>();
+        let (a, b) = prices.find_pair_with_sum(Cents(198)).unwrap();
+        assert_eq!(198, a.0 + b.0);
+        assert_eq!(None, prices.find_pair_with_sum(Cents(199)));
+        assert_eq!(1, prices.pairs_matching(|a, b| a.0 + b.0 == 2).count());
+    }
+
+    #[test]
+    fn strategies_near_limits() {
+        let nums = [200u8, 100, 250, 5];
+        for &s in &STRATEGIES {
+            assert_eq!(Some((250, 5)), nums.find_pair_with_sum_using(255, s));
+            assert_eq!(Some((100, 5)), nums.find_pair_with_sum_using(105, s));
+            assert_eq!(None, nums.find_pair_with_sum_using(44, s));
+            assert_eq!(None, nums.find_pair_with_sum_using(u8::MAX - 1, s));
+        }
+        let nums = [i8::MIN, 100, i8::MAX, -100];
+        for &s in &STRATEGIES {
+            assert_eq!(
+                Some((i8::MIN, i8::MAX)),
+                nums.find_pair_with_sum_using(-1, s)
+            );
+            assert_eq!(Some((100, -100)), nums.find_pair_with_sum_using(0, s));
+            assert_eq!(None, nums.find_pair_with_sum_using(i8::MIN, s));
+            assert_eq!(None, nums.find_pair_with_sum_using(i8::MAX, s));
+        }
+    }
+
+    #[test]
+    fn strategies_agree_on_big_input() {
+        let nums = (0..1000).map(|i| i * 3 - 1500).collect::<Vec<i64>>();
+        for &s in &STRATEGIES {
+            let (a, b) = nums.find_pair_with_sum_using(-9, s).unwrap();
+            assert_eq!(-9, a + b);
+            assert_eq!(None, nums.find_pair_with_sum_using(-8, s));
+        }
+    }
+
+    #[test]
+    fn k_sum_from_day_one() {
+        let nums = vec![1721, 979, 366, 299, 675, 1456];
+        assert_eq!(Some(vec![1721, 299]), nums.find_k_with_sum(2, 2020));
+        assert_eq!(Some(vec![979, 366, 675]), nums.find_k_with_sum(3, 2020));
+        assert_eq!(Some(vec![1, 2, 4]), nums.find_k_indices_with_sum(3, 2020));
+        assert_eq!(Some(vec![5]), nums.find_k_indices_with_sum(1, 1456));
+        assert_eq!(None, nums.find_k_with_sum(0, 2020));
+        assert_eq!(None, nums.find_k_with_sum(7, 2020));
+    }
+
+    #[test]
+    fn k_sum_negatives_and_duplicates() {
+        let nums = [10, -5, 4, 1, -7];
+        assert_eq!(Some(vec![-5, 4, 1]), nums.find_k_with_sum(3, 0));
+        assert_eq!(Some(vec![10, -5, 4, 1, -7]), nums.find_k_with_sum(5, 3));
+        assert_eq!(Some(vec![-5, -7]), nums.find_k_with_sum(2, -12));
+        let nums = [2u8, 2, 2, 3];
+        assert_eq!(Some(vec![0, 1, 2]), nums.find_k_indices_with_sum(3, 6));
+        assert_eq!(None, nums.find_k_indices_with_sum(4, 8));
+        assert_eq!(None, nums.find_k_indices_with_sum(2, 2));
+    }
+
+    #[test]
+    fn k_sum_near_limits() {
+        assert_eq!(None, [200u8, 100].find_k_with_sum(2, 50));
+        assert_eq!(
+            Some(vec![200, 55]),
+            [200u8, 100, 55].find_k_with_sum(2, 255)
+        );
+        assert_eq!(None, [200u8, 100, 60, 1].find_k_with_sum(3, 255));
+        assert_eq!(Some(vec![250, 1, 4]), [250u8, 1, 4].find_k_with_sum(3, 255));
+        let nums = [i8::MAX, i8::MIN, 100, -100, 1];
+        assert_eq!(Some(vec![i8::MAX, i8::MIN]), nums.find_k_with_sum(2, -1));
+        assert_eq!(Some(vec![-100, 1]), nums.find_k_with_sum(2, -99));
+        assert_eq!(None, nums.find_k_with_sum(2, i8::MIN));
+        assert_eq!(Some(vec![100, -100, 1]), nums.find_k_with_sum(3, 1));
+        assert_eq!(Some(vec![i8::MAX, -100, 1]), nums.find_k_with_sum(3, 28));
+        assert_eq!(Some(vec![i8::MIN, 1]), nums.find_k_with_sum(2, -127));
+        assert_eq!(
+            Some(vec![i8::MAX, i8::MIN, 100, -100]),
+            nums.find_k_with_sum(4, -1)
+        );
+        assert_eq!(
+            Some(vec![-128, 1, 127]),
+            [-128i8, 1, 127].find_k_with_sum(3, 0)
+        );
+        let nums = [-128i8, -100, 127, 127];
+        assert_eq!(
+            Some(vec![-128, -100, 127, 127]),
+            nums.find_k_with_sum(4, 26)
+        );
+        assert_eq!(None, nums.find_k_with_sum(3, i8::MIN));
+    }
+
+    #[test]
+    fn k_sum_agrees_with_brute_force() {
+        let mut rng = Rng::new(28);
+        let values = [i8::MIN, -127, -100, -1, 0, 1, 2, 100, 126, i8::MAX];
+        for _ in 0..200 {
+            let nums = (0..7).map(|_| *rng.pick(&values)).collect::<Vec<_>>();
+            for k in 1..=5 {
+                for &sum in &[i8::MIN, -101, -1, 0, 1, 28, i8::MAX] {
+                    let exists = (0u32..(1 << nums.len()))
+                        .filter(|set| set.count_ones() as usize == k)
+                        .any(|set| {
+                            (0..nums.len())
+                                .filter(|i| set & (1 << i) != 0)
+                                .map(|i| nums[i] as i32)
+                                .sum::<i32>()
+                                == sum as i32
+                        });
+                    let found = nums.find_k_with_sum(k, sum);
+                    assert_eq!(exists, found.is_some(), "{:?} {} {}", nums, k, sum);
+                    if let Some(found) = found {
+                        assert_eq!(sum as i32, found.iter().map(|&n| n as i32).sum());
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn all_pairs() {
+        let nums = [1, 3, 2, 2, -1, 5];
+        assert_eq!(
+            vec![(0, 1), (2, 3), (4, 5)],
+            nums.pairs_with_sum(4).collect::<Vec<_>>()
+        );
+        assert_eq!(0, nums.pairs_with_sum(100).count());
+        assert_eq!(
+            vec![(0, 2)],
+            [200u8, 100, 55].pairs_with_sum(255).collect::<Vec<_>>()
+        );
+        assert_eq!(0, [200u8, 100].pairs_with_sum(44).count());
+        assert_eq!(
+            vec![(2, 3)],
+            nums.pairs_matching(|a, b| a == b).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![(1, 5), (2, 5), (3, 5)],
+            nums.pairs_matching(|a, b| a * b > 9).collect::<Vec<_>>()
+        );
+        assert_eq!(0, nums[..1].pairs_matching(|_, _| true).count());
+        assert_eq!(15, nums.pairs_matching(|_, _| true).count());
+    }
+
+    #[test]
+    fn count_pairs() {
+        let nums = [1, 3, 2, 2, -1, 5];
+        assert_eq!(3, nums.count_pairs_with_sum(4));
+        assert_eq!(0, nums.count_pairs_with_sum(100));
+        assert_eq!(3, [2u8, 2, 2].count_pairs_with_sum(4));
+        assert_eq!(9, [3u8, 3, 1, 1, 3, 1].count_pairs_with_sum(4));
+        for sum in -3..12 {
+            assert_eq!(
+                nums.pairs_with_sum(sum).count(),
+                nums.count_pairs_with_sum(sum)
+            );
+        }
+    }
+
+    const XMAS_EXAMPLE: [u64; 20] = [
+        35, 20, 15, 25, 47, 40, 62, 55, 65, 95, 102, 117, 150, 182, 127, 219, 299, 277, 309, 576,
+    ];
+
+    #[test]
+    fn window_validator() {
+        assert_eq!(Some(14), find_first_invalid(&XMAS_EXAMPLE, 5));
+        assert_eq!(127, XMAS_EXAMPLE[14]);
+        assert_eq!(None, find_first_invalid(&XMAS_EXAMPLE[..14], 5));
+        assert_eq!(None, find_first_invalid(&XMAS_EXAMPLE[..5], 5));
+        let mut v = WindowValidator::new(2);
+        assert!(v.push(-3));
+        assert!(v.push(1));
+        assert!(v.push(-2));
+        assert!(!v.push(-2));
+        // a repeated value can pair with itself, until one copy slides out
+        let mut v = WindowValidator::new(3);
+        for n in &[4u8, 4, 1] {
+            assert!(v.push(*n));
+        }
+        assert!(v.push(8));
+        assert!(!v.push(8));
+        assert!(v.push(16));
+        assert!(!v.push(255));
+    }
+
+    #[test]
+    fn contiguous_sum() {
+        let r = find_contiguous_with_sum(&XMAS_EXAMPLE, 127, 2).unwrap();
+        assert_eq!(2..6, r);
+        assert_eq!(vec![15, 25, 47, 40], XMAS_EXAMPLE[r].to_vec());
+        // past the first run, 127 itself is a run of one
+        assert_eq!(
+            Some(8..9),
+            find_contiguous_with_sum(&XMAS_EXAMPLE[6..], 127, 1)
+        );
+        assert_eq!(None, find_contiguous_with_sum(&XMAS_EXAMPLE, 1, 1));
+        assert_eq!(Some(0..2), find_contiguous_with_sum(&[-1, 1, 0], 0, 2));
+        assert_eq!(Some(0..3), find_contiguous_with_sum(&[-1, 1, 0], 0, 3));
+    }
+
+    #[test]
+    fn contiguous_sum_near_limits() {
+        assert_eq!(
+            Some(1..3),
+            find_contiguous_with_sum(&[200u8, 100, 50], 150, 1)
+        );
+        let nums = [u64::MAX, 1, u64::MAX];
+        assert_eq!(Some(0..1), find_contiguous_with_sum(&nums, u64::MAX, 1));
+        assert_eq!(None, find_contiguous_with_sum(&nums, u64::MAX, 2));
+        assert_eq!(
+            Some(1..3),
+            find_contiguous_with_sum(&[100i8, 100, -100, -100], 0, 2)
+        );
+        // the running total passes far beyond an i8 before coming back
+        let nums = [i8::MAX, i8::MAX, i8::MAX, i8::MIN, i8::MIN, i8::MIN];
+        assert_eq!(Some(0..6), find_contiguous_with_sum(&nums, -3, 6));
+        assert_eq!(None, find_contiguous_with_sum(&nums, i8::MIN, 4));
+    }
 }
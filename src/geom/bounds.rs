@@ -0,0 +1,199 @@
+use crate::geom::Coords;
+use num_traits::{NumCast, One, ToPrimitive};
+
+/// I am an axis-aligned bounding box, including both my `min` and `max` corners, for any `Coords`
+/// type.
+///
+/// # Examples
+///
+/// ```
+/// use aoc_2020::geom::bounds::Bounds;
+/// use aoc_2020::vector_type;
+///
+/// vector_type!(Vec2, i32, x, y);
+///
+/// let b = Bounds::from_points(vec![Vec2::new(1, 5), Vec2::new(3, 2)]).unwrap();
+/// assert_eq!(Vec2::new(1, 2), b.min());
+/// assert_eq!(Vec2::new(3, 5), b.max());
+/// assert_eq!(12, b.volume());
+/// assert!(b.contains(&Vec2::new(2, 2)));
+/// assert!(!b.contains(&Vec2::new(0, 2)));
+/// assert_eq!(30, b.expand(1).volume());
+/// assert_eq!(Some(Vec2::new(2, 2)), b.iter().nth(1));
+/// ```
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Bounds<P, const N: usize> {
+    min: P,
+    max: P,
+}
+
+impl<P, const N: usize> Bounds<P, N>
+where
+    P: Coords<N>,
+{
+    /// Build the bounds spanning two corners, which need not be the min and max.
+    pub fn new(a: P, b: P) -> Bounds<P, N> {
+        let (a, b) = (a.coords(), b.coords());
+        let mut min = a;
+        let mut max = b;
+        for i in 0..N {
+            min[i] = a[i].min(b[i]);
+            max[i] = a[i].max(b[i]);
+        }
+        Bounds {
+            min: P::from_coords(min),
+            max: P::from_coords(max),
+        }
+    }
+
+    /// Build the smallest bounds containing every point, or `None` if there aren't any.
+    pub fn from_points<I>(points: I) -> Option<Bounds<P, N>>
+    where
+        I: IntoIterator<Item = P>,
+    {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        Some(points.fold(Bounds::new(first, first), |b, p| b.including(p)))
+    }
+
+    pub fn min(&self) -> P {
+        self.min
+    }
+
+    pub fn max(&self) -> P {
+        self.max
+    }
+
+    /// The smallest bounds containing both me and the passed point.
+    pub fn including(&self, p: P) -> Bounds<P, N> {
+        Bounds::new(Bounds::new(self.min, p).min, Bounds::new(self.max, p).max)
+    }
+
+    pub fn contains(&self, p: &P) -> bool {
+        let (min, max, p) = (self.min.coords(), self.max.coords(), p.coords());
+        (0..N).all(|i| min[i] <= p[i] && p[i] <= max[i])
+    }
+
+    /// Grow by `margin` in every direction along every axis.
+    pub fn expand(&self, margin: usize) -> Bounds<P, N> {
+        let margin =
+            <P::Coord as NumCast>::from(margin).expect("Margin doesn't fit in the coordinate type");
+        let (mut min, mut max) = (self.min.coords(), self.max.coords());
+        for i in 0..N {
+            min[i] = min[i] - margin;
+            max[i] = max[i] + margin;
+        }
+        Bounds {
+            min: P::from_coords(min),
+            max: P::from_coords(max),
+        }
+    }
+
+    /// The number of lattice points within the bounds.
+    pub fn volume(&self) -> usize {
+        let (min, max) = (self.min.coords(), self.max.coords());
+        (0..N)
+            .map(|i| (max[i] - min[i]).to_usize().unwrap() + 1)
+            .product()
+    }
+
+    /// Iterate over every lattice point within the bounds, in row-major order: the first component
+    /// varies fastest and the last slowest, as when reading a grid left to right, top to bottom.
+    pub fn iter(&self) -> BoundsIter<P, N> {
+        BoundsIter {
+            bounds: *self,
+            next: Some(self.min.coords()),
+        }
+    }
+}
+
+pub struct BoundsIter<P, const N: usize>
+where
+    P: Coords<N>,
+{
+    bounds: Bounds<P, N>,
+    next: Option<[P::Coord; N]>,
+}
+
+impl<P, const N: usize> Iterator for BoundsIter<P, N>
+where
+    P: Coords<N>,
+{
+    type Item = P;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let curr = self.next?;
+        let (min, max) = (self.bounds.min.coords(), self.bounds.max.coords());
+        let mut next = curr;
+        self.next = None;
+        for i in 0..N {
+            if next[i] < max[i] {
+                next[i] = next[i] + P::Coord::one();
+                self.next = Some(next);
+                break;
+            }
+            next[i] = min[i];
+        }
+        Some(P::from_coords(curr))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::vector_type;
+
+    vector_type!(Vec2, i32, x, y);
+    vector_type!(Vec3, isize, x, y, z);
+
+    #[test]
+    fn from_points() {
+        assert_eq!(None, Bounds::<Vec2, 2>::from_points(vec![]));
+        let b = Bounds::from_points(vec![Vec2::new(-1, 4)]).unwrap();
+        assert_eq!(b.min(), b.max());
+        assert_eq!(1, b.volume());
+        let b = Bounds::from_points(vec![
+            Vec3::new(1, -2, 3),
+            Vec3::new(-1, 2, 0),
+            Vec3::new(0, 0, 5),
+        ])
+        .unwrap();
+        assert_eq!(Vec3::new(-1, -2, 0), b.min());
+        assert_eq!(Vec3::new(1, 2, 5), b.max());
+        assert_eq!(3 * 5 * 6, b.volume());
+        assert_eq!(b, Bounds::new(Vec3::new(1, -2, 5), Vec3::new(-1, 2, 0)));
+    }
+
+    #[test]
+    fn contains_and_expand() {
+        let b = Bounds::new(Vec2::new(0, 0), Vec2::new(2, 1));
+        assert!(b.contains(&Vec2::new(0, 0)));
+        assert!(b.contains(&Vec2::new(2, 1)));
+        assert!(!b.contains(&Vec2::new(3, 1)));
+        assert!(!b.contains(&Vec2::new(1, -1)));
+        let e = b.expand(2);
+        assert_eq!(Vec2::new(-2, -2), e.min());
+        assert_eq!(Vec2::new(4, 3), e.max());
+        assert!(e.contains(&Vec2::new(1, -1)));
+        assert_eq!(b, b.expand(0));
+    }
+
+    #[test]
+    fn iter_row_major() {
+        let b = Bounds::new(Vec2::new(0, 5), Vec2::new(2, 6));
+        assert_eq!(
+            vec![
+                Vec2::new(0, 5),
+                Vec2::new(1, 5),
+                Vec2::new(2, 5),
+                Vec2::new(0, 6),
+                Vec2::new(1, 6),
+                Vec2::new(2, 6),
+            ],
+            b.iter().collect::<Vec<_>>()
+        );
+        let b = Bounds::new(Vec3::new(-1, -1, -1), Vec3::new(1, 1, 1));
+        assert_eq!(b.volume(), b.iter().count());
+        assert!(b.iter().all(|p| b.contains(&p)));
+    }
+}
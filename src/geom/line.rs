@@ -0,0 +1,196 @@
+use crate::geom::Coords;
+use num_traits::{PrimInt, Signed, ToPrimitive, Zero};
+
+fn gcd<C>(a: C, b: C) -> C
+where
+    C: PrimInt + Signed,
+{
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while !b.is_zero() {
+        let t = a % b;
+        a = b;
+        b = t;
+    }
+    a
+}
+
+/// I iterate over every lattice point exactly on the segment between two points, inclusive of both
+/// ends. The step between points is the overall delta divided by the GCD of its components, so
+/// unlike Bresenham's line, every point is exactly on the line, and lines which aren't horizontal,
+/// vertical, or diagonal may skip over quite a lot of space.
+///
+/// # Examples
+///
+/// ```
+/// use aoc_2020::geom::point::Point;
+/// use aoc_2020::geom::Coords;
+///
+/// let from = Point::new([0, 0]);
+/// assert_eq!(
+///     vec![Point::new([0, 0]), Point::new([2, -1]), Point::new([4, -2])],
+///     from.line_to(&Point::new([4, -2])).collect::<Vec<_>>()
+/// );
+/// ```
+pub struct LatticeLine<P, const N: usize>
+where
+    P: Coords<N>,
+{
+    next: [P::Coord; N],
+    step: [P::Coord; N],
+    remaining: usize,
+}
+
+impl<P, const N: usize> LatticeLine<P, N>
+where
+    P: Coords<N>,
+{
+    pub fn new(from: &P, to: &P) -> LatticeLine<P, N> {
+        let (from, to) = (from.coords(), to.coords());
+        let mut step = to;
+        for (s, f) in step.iter_mut().zip(from.iter()) {
+            *s = *s - *f;
+        }
+        let g = step.iter().fold(P::Coord::zero(), |g, &s| gcd(g, s));
+        if !g.is_zero() {
+            for s in step.iter_mut() {
+                *s = *s / g;
+            }
+        }
+        LatticeLine {
+            next: from,
+            step,
+            remaining: g.to_usize().unwrap() + 1,
+        }
+    }
+}
+
+impl<P, const N: usize> Iterator for LatticeLine<P, N>
+where
+    P: Coords<N>,
+{
+    type Item = P;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        let curr = self.next;
+        for (n, s) in self.next.iter_mut().zip(self.step.iter()) {
+            *n = *n + *s;
+        }
+        Some(P::from_coords(curr))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+/// I iterate, forever, over the points reached by repeatedly stepping away from an origin, which is
+/// not itself included. Pair me with `take_while` to scan along a line of sight.
+///
+/// # Examples
+///
+/// ```
+/// use aoc_2020::geom::point::Point;
+/// use aoc_2020::geom::Coords;
+///
+/// let from = Point::new([5, 5]);
+/// assert_eq!(
+///     vec![Point::new([6, 4]), Point::new([7, 3]), Point::new([8, 2])],
+///     from.ray(&Point::new([1, -1])).take(3).collect::<Vec<_>>()
+/// );
+/// ```
+pub struct Ray<P, const N: usize>
+where
+    P: Coords<N>,
+{
+    curr: [P::Coord; N],
+    step: [P::Coord; N],
+}
+
+impl<P, const N: usize> Ray<P, N>
+where
+    P: Coords<N>,
+{
+    pub fn new(origin: &P, step: &P) -> Ray<P, N> {
+        Ray {
+            curr: origin.coords(),
+            step: step.coords(),
+        }
+    }
+}
+
+impl<P, const N: usize> Iterator for Ray<P, N>
+where
+    P: Coords<N>,
+{
+    type Item = P;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for (c, s) in self.curr.iter_mut().zip(self.step.iter()) {
+            *c = *c + *s;
+        }
+        Some(P::from_coords(self.curr))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::geom::point::Point;
+    use crate::vector_type;
+
+    vector_type!(Vec3, i32, x, y, z);
+
+    #[test]
+    fn lines() {
+        let a = Vec3::new(1, 1, 1);
+        assert_eq!(vec![a], a.line_to(&a).collect::<Vec<_>>());
+        assert_eq!(
+            vec![a, Vec3::new(2, 1, 1), Vec3::new(3, 1, 1)],
+            a.line_to(&Vec3::new(3, 1, 1)).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![a, Vec3::new(0, 0, 0), Vec3::new(-1, -1, -1)],
+            a.line_to(&Vec3::new(-1, -1, -1)).collect::<Vec<_>>()
+        );
+        // no lattice points strictly between
+        assert_eq!(2, a.line_to(&Vec3::new(3, 4, 6)).count());
+        let line = Point::new([0, 0]).line_to(&Point::new([6, 9]));
+        assert_eq!(4, line.size_hint().0);
+        assert_eq!(
+            vec![[0, 0], [2, 3], [4, 6], [6, 9]],
+            line.map(|p| p.coords()).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn rays() {
+        let r = Vec3::origin().ray(&Vec3::new(0, -2, 1));
+        assert_eq!(
+            vec![Vec3::new(0, -2, 1), Vec3::new(0, -4, 2)],
+            r.take(2).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Some(Point::new([3, 3])),
+            Point::new([0, 0])
+                .ray(&Point::new([1, 1]))
+                .find(|p| p[0] * p[1] > 5)
+        );
+    }
+
+    #[test]
+    fn distances() {
+        let a = Vec3::new(1, -2, 3);
+        let b = Vec3::new(-1, 2, 4);
+        assert_eq!(7, a.manhattan_distance(&b));
+        assert_eq!(4, a.chebyshev_distance(&b));
+        assert_eq!(21, a.squared_euclidean_distance(&b));
+        let a = Point::new([1, -2, 3]);
+        let b = Point::new([-1, 2, 4]);
+        assert_eq!(4, a.chebyshev_distance(&b));
+        assert_eq!(21, a.squared_euclidean_distance(&b));
+    }
+}
@@ -1,43 +1,166 @@
+use crate::geom::line::{LatticeLine, Ray};
+use crate::geom::neighborhood::{MooreNeighbors, VonNeumannNeighbors};
+use num_traits::{PrimInt, Signed};
+
+pub mod bounds;
+pub mod line;
+pub mod neighborhood;
+pub mod point;
+pub mod rotation;
+
+/// I expose a vector type's components as a fixed-size array, so algorithms can be written once for
+/// any number of dimensions. `vector_type!` implements me for the types it generates.
+pub trait Coords<const N: usize>: Copy {
+    type Coord: PrimInt + Signed;
+
+    fn coords(&self) -> [Self::Coord; N];
+
+    fn from_coords(coords: [Self::Coord; N]) -> Self;
+
+    /// Iterate over every other point within `radius` steps along each axis, diagonals included.
+    fn moore_neighbors(&self, radius: usize) -> MooreNeighbors<Self, N> {
+        MooreNeighbors::new(*self, radius)
+    }
+
+    /// Iterate over every other point within a Manhattan distance of `radius`.
+    fn von_neumann_neighbors(&self, radius: usize) -> VonNeumannNeighbors<Self, N> {
+        VonNeumannNeighbors::new(*self, radius)
+    }
+
+    /// Iterate over the lattice points exactly on the segment from me to `to`, inclusive.
+    fn line_to(&self, to: &Self) -> LatticeLine<Self, N> {
+        LatticeLine::new(self, to)
+    }
+
+    /// Iterate, forever, over the points `self + step`, `self + 2 * step`, and so on.
+    fn ray(&self, step: &Self) -> Ray<Self, N> {
+        Ray::new(self, step)
+    }
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __count_idents {
+    () => { 0usize };
+    ( $h:ident $( $t:ident )* ) => { 1usize + $crate::__count_idents!($( $t )*) };
+}
+
+/// I generate a `Copy`-able vector type named `$n` with components of type `$t` (which must be a
+/// signed integer), named by the remaining identifiers. Put a visibility (e.g., `pub`) before the
+/// name to expose the type, its components, and its methods.
+///
+/// Along with a handful of methods, the type supports `+`, `-`, negation, multiplication by a
+/// scalar, `+=`, and `-=`, and it implements `Display` and `FromStr` using the `(x,y,z)` form.
+///
+/// # Examples
+///
+/// ```
+/// use aoc_2020::vector_type;
+///
+/// vector_type!(pub Vec3, i32, x, y, z);
+///
+/// let mut v = Vec3::new(1, -2, 3);
+/// v += Vec3::new(1, 1, 1) * 2;
+/// assert_eq!(Vec3::new(3, 0, 5), v);
+/// assert_eq!(Vec3::new(-3, 0, -5), -v);
+/// assert_eq!(Vec3::new(1, 0, 1), v.signum());
+/// assert_eq!(5, v.chebyshev_distance(&Vec3::origin()));
+/// assert_eq!(34, v.squared_euclidean_distance(&Vec3::origin()));
+/// assert_eq!("(3,0,5)", v.to_string());
+/// assert_eq!(Ok(v), "(3, 0, 5)".parse());
+/// ```
 #[macro_export]
 macro_rules! vector_type {
-    ( $n:ident, $t:ty, $f:ident $( ,$d:ident )* ) => {
+    ( $v:vis $n:ident, $t:ty, $f:ident $( ,$d:ident )* ) => {
         #[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone)]
-        struct $n {
-            $f: $t
-            $( , $d: $t )*
+        $v struct $n {
+            $v $f: $t
+            $( , $v $d: $t )*
         }
 
         #[allow(unused)]
         impl $n {
-            fn new($f: $t $( , $d: $t )*) -> Self {
+            $v fn new($f: $t $( , $d: $t )*) -> Self {
                 $n { $f $( , $d )* }
             }
 
-            fn origin() -> Self {
+            $v fn origin() -> Self {
                 $n { $f: 0 $( , $d: 0 )* }
             }
 
-            fn rectilinear_min(&self, other: &Self) -> Self {
+            $v fn rectilinear_min(&self, other: &Self) -> Self {
                 $n { $f: self.$f.min(other.$f)
                 $( , $d: self.$d.min(other.$d) )*
                 }
             }
 
-            fn rectilinear_max(&self, other: &Self) -> Self {
+            $v fn rectilinear_max(&self, other: &Self) -> Self {
                 $n { $f: self.$f.max(other.$f)
                 $( , $d: self.$d.max(other.$d) )*
                 }
             }
 
-            fn manhattan_distance(&self, p: &Self) -> usize {
-                let mut d = (self.$f - p.$f).abs() as usize;
-                $( d += (self.$d - p.$d).abs() as usize; )*
+            $v fn manhattan_distance(&self, p: &Self) -> usize {
+                let mut d = (self.$f - p.$f).unsigned_abs() as usize;
+                $( d += (self.$d - p.$d).unsigned_abs() as usize; )*
                 d
             }
 
+            /// The largest difference along any one axis: the number of king's moves between us.
+            $v fn chebyshev_distance(&self, p: &Self) -> usize {
+                let mut d = (self.$f - p.$f).unsigned_abs() as usize;
+                $( d = d.max((self.$d - p.$d).unsigned_abs() as usize); )*
+                d
+            }
+
+            /// The square of the straight-line distance, which stays an integer.
+            $v fn squared_euclidean_distance(&self, p: &Self) -> usize {
+                let d = (self.$f - p.$f).unsigned_abs() as usize;
+                let mut sum = d * d;
+                $(
+                    let d = (self.$d - p.$d).unsigned_abs() as usize;
+                    sum += d * d;
+                )*
+                sum
+            }
+
+            /// The component-wise absolute value.
+            $v fn abs(&self) -> Self {
+                $n { $f: self.$f.abs() $( , $d: self.$d.abs() )* }
+            }
+
+            /// The component-wise sign (`-1`, `0`, or `1`).
+            $v fn signum(&self) -> Self {
+                $n { $f: self.$f.signum() $( , $d: self.$d.signum() )* }
+            }
+        }
+
+        impl $crate::geom::Coords<{ $crate::__count_idents!($f $( $d )*) }> for $n {
+            type Coord = $t;
+
+            fn coords(&self) -> [$t; $crate::__count_idents!($f $( $d )*)] {
+                [self.$f $( , self.$d )*]
+            }
+
+            fn from_coords(coords: [$t; $crate::__count_idents!($f $( $d )*)]) -> Self {
+                let [$f $( , $d )*] = coords;
+                $n { $f $( , $d )* }
+            }
+        }
+
+        impl From<$n> for $crate::geom::point::Point<{ $crate::__count_idents!($f $( $d )*) }> {
+            fn from(v: $n) -> Self {
+                Self::new([v.$f as isize $( , v.$d as isize )*])
+            }
+        }
+
+        impl From<$crate::geom::point::Point<{ $crate::__count_idents!($f $( $d )*) }>> for $n {
+            fn from(p: $crate::geom::point::Point<{ $crate::__count_idents!($f $( $d )*) }>) -> Self {
+                let [$f $( , $d )*] = $crate::geom::Coords::coords(&p);
+                $n { $f: $f as $t $( , $d: $d as $t )* }
+            }
         }
 
-        #[allow(unused)]
         impl std::ops::Add for $n {
             type Output = $n;
 
@@ -48,6 +171,46 @@ macro_rules! vector_type {
             }
         }
 
+        impl std::ops::Sub for $n {
+            type Output = $n;
+
+            fn sub(self, rhs: Self) -> Self::Output {
+                $n { $f: self.$f - rhs.$f
+                $( , $d: self.$d - rhs.$d )*
+                }
+            }
+        }
+
+        impl std::ops::Neg for $n {
+            type Output = $n;
+
+            fn neg(self) -> Self::Output {
+                $n { $f: -self.$f $( , $d: -self.$d )* }
+            }
+        }
+
+        impl std::ops::Mul<$t> for $n {
+            type Output = $n;
+
+            fn mul(self, rhs: $t) -> Self::Output {
+                $n { $f: self.$f * rhs $( , $d: self.$d * rhs )* }
+            }
+        }
+
+        impl std::ops::AddAssign for $n {
+            fn add_assign(&mut self, rhs: Self) {
+                self.$f += rhs.$f;
+                $( self.$d += rhs.$d; )*
+            }
+        }
+
+        impl std::ops::SubAssign for $n {
+            fn sub_assign(&mut self, rhs: Self) {
+                self.$f -= rhs.$f;
+                $( self.$d -= rhs.$d; )*
+            }
+        }
+
         impl std::fmt::Display for $n {
             fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
                 write!(f, "({}", self.$f)?;
@@ -55,6 +218,31 @@ macro_rules! vector_type {
                 write!(f, ")")
             }
         }
+
+        impl std::str::FromStr for $n {
+            type Err = String;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let inner = s
+                    .trim()
+                    .strip_prefix('(')
+                    .and_then(|s| s.strip_suffix(')'))
+                    .ok_or_else(|| format!("Expected '(...)', but found '{}'", s))?;
+                let mut parts = inner.split(',');
+                let mut next = || match parts.next() {
+                    Some(p) => p
+                        .trim()
+                        .parse::<$t>()
+                        .map_err(|e| format!("Unparseable '{}' in '{}': {}", p, s, e)),
+                    None => Err(format!("Too few components in '{}'", s)),
+                };
+                let v = $n { $f: next()? $( , $d: next()? )* };
+                if parts.next().is_some() {
+                    return Err(format!("Too many components in '{}'", s));
+                }
+                Ok(v)
+            }
+        }
     };
 }
 
@@ -92,6 +280,54 @@ mod test {
         assert_eq!(Vec2::new(4, 6), a + b);
     }
 
+    #[test]
+    fn test_sub_neg_mul() {
+        let a = Vec2::new(1, 2);
+        let b = Vec2::new(3, -4);
+        assert_eq!(Vec2::new(-2, 6), a - b);
+        assert_eq!(Vec2::new(-3, 4), -b);
+        assert_eq!(Vec2::new(-3, -6), a * -3);
+        assert_eq!(Vec2::origin(), a * 0);
+    }
+
+    #[test]
+    fn test_assign() {
+        let mut a = Vec2::new(1, 2);
+        a += Vec2::new(3, -4);
+        assert_eq!(Vec2::new(4, -2), a);
+        a -= Vec2::new(4, 4);
+        assert_eq!(Vec2::new(0, -6), a);
+    }
+
+    #[test]
+    fn test_abs_signum() {
+        let a = Vec3::new(-7, 0, 3);
+        assert_eq!(Vec3::new(7, 0, 3), a.abs());
+        assert_eq!(Vec3::new(-1, 0, 1), a.signum());
+    }
+
+    #[test]
+    fn test_from_str() {
+        let v = Vec3::new(1, -22, 333);
+        assert_eq!(Ok(v), v.to_string().parse());
+        assert_eq!(Ok(v), " ( 1 , -22,333 ) ".parse());
+        assert!("1,-22,333".parse::<Vec3>().is_err());
+        assert!("(1,-22)".parse::<Vec3>().is_err());
+        assert!("(1,-22,333,4)".parse::<Vec3>().is_err());
+        assert!("(1,x,333)".parse::<Vec3>().is_err());
+    }
+
+    mod visibility {
+        vector_type!(pub Exposed, i64, a, b);
+    }
+
+    #[test]
+    fn test_pub() {
+        let v = visibility::Exposed::new(1, 2);
+        assert_eq!(2, v.b);
+        assert_eq!(3, v.manhattan_distance(&visibility::Exposed::origin()));
+    }
+
     #[test]
     fn test_rectilinear_min() {
         let a = Vec2::new(1, 2);
@@ -122,4 +358,13 @@ mod test {
         assert_eq!(25, Vec2::new(-17, 8).manhattan_distance(&origin));
         assert_eq!(25, Vec2::new(-17, -8).manhattan_distance(&origin));
     }
+
+    #[test]
+    fn test_chebyshev_and_euclidean_distance() {
+        let a = Vec2::new(17, -8);
+        assert_eq!(17, a.chebyshev_distance(&Vec2::origin()));
+        assert_eq!(8, a.chebyshev_distance(&Vec2::new(10, 0)));
+        assert_eq!(353, a.squared_euclidean_distance(&Vec2::origin()));
+        assert_eq!(0, a.squared_euclidean_distance(&a));
+    }
 }
@@ -0,0 +1,183 @@
+use crate::geom::Coords;
+use num_traits::{NumCast, PrimInt, Signed, Zero};
+
+/// I count through every offset in `[-radius, radius]` along each of `N` axes, first axis fastest,
+/// without allocating.
+struct Offsets<C, const N: usize> {
+    radius: C,
+    offset: [C; N],
+    done: bool,
+}
+
+impl<C, const N: usize> Offsets<C, N>
+where
+    C: PrimInt + Signed,
+{
+    fn new(radius: usize) -> Offsets<C, N> {
+        let radius =
+            <C as NumCast>::from(radius).expect("Radius doesn't fit in the coordinate type");
+        Offsets {
+            radius,
+            offset: [-radius; N],
+            done: N == 0,
+        }
+    }
+
+    fn advance(&mut self) {
+        for o in self.offset.iter_mut() {
+            if *o < self.radius {
+                *o = *o + C::one();
+                return;
+            }
+            *o = -self.radius;
+        }
+        self.done = true;
+    }
+}
+
+impl<C, const N: usize> Iterator for Offsets<C, N>
+where
+    C: PrimInt + Signed,
+{
+    type Item = [C; N];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            let o = self.offset;
+            self.advance();
+            if o.iter().any(|c| !c.is_zero()) {
+                return Some(o);
+            }
+        }
+        None
+    }
+}
+
+fn offset_from<P, const N: usize>(center: &P, offset: [P::Coord; N]) -> P
+where
+    P: Coords<N>,
+{
+    let mut coords = center.coords();
+    for (c, o) in coords.iter_mut().zip(offset.iter()) {
+        *c = *c + *o;
+    }
+    P::from_coords(coords)
+}
+
+/// I iterate over the Moore neighborhood of a point: every other point within `radius` steps along
+/// each axis, diagonals included. In two dimensions with a radius of one, that's the eight
+/// surrounding cells; in four dimensions, it's 80.
+pub struct MooreNeighbors<P, const N: usize>
+where
+    P: Coords<N>,
+{
+    center: P,
+    offsets: Offsets<P::Coord, N>,
+}
+
+impl<P, const N: usize> MooreNeighbors<P, N>
+where
+    P: Coords<N>,
+{
+    pub fn new(center: P, radius: usize) -> MooreNeighbors<P, N> {
+        MooreNeighbors {
+            center,
+            offsets: Offsets::new(radius),
+        }
+    }
+}
+
+impl<P, const N: usize> Iterator for MooreNeighbors<P, N>
+where
+    P: Coords<N>,
+{
+    type Item = P;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.offsets.next().map(|o| offset_from(&self.center, o))
+    }
+}
+
+/// I iterate over the von Neumann neighborhood of a point: every other point within a Manhattan
+/// distance of `radius`. In two dimensions with a radius of one, that's the four orthogonally
+/// adjacent cells.
+pub struct VonNeumannNeighbors<P, const N: usize>
+where
+    P: Coords<N>,
+{
+    center: P,
+    radius: P::Coord,
+    offsets: Offsets<P::Coord, N>,
+}
+
+impl<P, const N: usize> VonNeumannNeighbors<P, N>
+where
+    P: Coords<N>,
+{
+    pub fn new(center: P, radius: usize) -> VonNeumannNeighbors<P, N> {
+        VonNeumannNeighbors {
+            center,
+            radius: <P::Coord as NumCast>::from(radius)
+                .expect("Radius doesn't fit in the coordinate type"),
+            offsets: Offsets::new(radius),
+        }
+    }
+}
+
+impl<P, const N: usize> Iterator for VonNeumannNeighbors<P, N>
+where
+    P: Coords<N>,
+{
+    type Item = P;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let radius = self.radius;
+        self.offsets
+            .find(|o| o.iter().fold(P::Coord::zero(), |d, c| d + c.abs()) <= radius)
+            .map(|o| offset_from(&self.center, o))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::geom::Coords;
+    use crate::vector_type;
+    use std::collections::HashSet;
+
+    vector_type!(Vec2, i32, x, y);
+    vector_type!(Vec3, i64, x, y, z);
+
+    #[test]
+    fn moore() {
+        let p = Vec2::new(5, -5);
+        let ns = p.moore_neighbors(1).collect::<Vec<_>>();
+        assert_eq!(8, ns.len());
+        assert_eq!(Vec2::new(4, -6), ns[0]);
+        assert_eq!(Vec2::new(5, -6), ns[1]);
+        assert_eq!(Vec2::new(6, -4), ns[7]);
+        assert!(!ns.contains(&p));
+        assert_eq!(24, p.moore_neighbors(2).count());
+        assert_eq!(0, p.moore_neighbors(0).count());
+        assert_eq!(26, Vec3::origin().moore_neighbors(1).count());
+        assert_eq!(124, Vec3::origin().moore_neighbors(2).count());
+    }
+
+    #[test]
+    fn von_neumann() {
+        let p = Vec2::new(5, -5);
+        let ns = p.von_neumann_neighbors(1).collect::<HashSet<_>>();
+        let expected = vec![
+            Vec2::new(4, -5),
+            Vec2::new(6, -5),
+            Vec2::new(5, -6),
+            Vec2::new(5, -4),
+        ];
+        assert_eq!(expected.into_iter().collect::<HashSet<_>>(), ns);
+        assert_eq!(12, p.von_neumann_neighbors(2).count());
+        assert!(p
+            .von_neumann_neighbors(3)
+            .all(|n| n.manhattan_distance(&p) <= 3));
+        assert_eq!(6, Vec3::origin().von_neumann_neighbors(1).count());
+        assert_eq!(24, Vec3::origin().von_neumann_neighbors(2).count());
+    }
+}
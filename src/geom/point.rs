@@ -0,0 +1,284 @@
+use crate::geom::Coords;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, AddAssign, Index, IndexMut, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+/// I am a point (or vector) with `N` `isize` components. I offer the same operations as the types
+/// `vector_type!` generates, but since my dimension is a type parameter, code which is generic over
+/// dimension can use me directly. `vector_type!` types convert to and from me with `From`.
+///
+/// # Examples
+///
+/// ```
+/// use aoc_2020::geom::point::Point;
+///
+/// let mut p = Point::new([1, -2, 3]);
+/// p += Point::new([1, 1, 1]) * 2;
+/// assert_eq!(Point::new([3, 0, 5]), p);
+/// assert_eq!(-5, (-p)[2]);
+/// assert_eq!(8, p.manhattan_distance(&Point::origin()));
+/// assert_eq!("(3,0,5)", p.to_string());
+/// assert_eq!(Ok(p), "(3, 0, 5)".parse());
+/// ```
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone)]
+pub struct Point<const N: usize>([isize; N]);
+
+impl<const N: usize> Point<N> {
+    pub fn new(coords: [isize; N]) -> Point<N> {
+        Point(coords)
+    }
+
+    pub fn origin() -> Point<N> {
+        Point([0; N])
+    }
+
+    fn zip_with<F>(&self, other: &Point<N>, f: F) -> Point<N>
+    where
+        F: Fn(isize, isize) -> isize,
+    {
+        let mut coords = self.0;
+        for (c, &o) in coords.iter_mut().zip(other.0.iter()) {
+            *c = f(*c, o);
+        }
+        Point(coords)
+    }
+
+    fn map<F>(&self, f: F) -> Point<N>
+    where
+        F: Fn(isize) -> isize,
+    {
+        Point(self.0.map(f))
+    }
+
+    pub fn rectilinear_min(&self, other: &Point<N>) -> Point<N> {
+        self.zip_with(other, isize::min)
+    }
+
+    pub fn rectilinear_max(&self, other: &Point<N>) -> Point<N> {
+        self.zip_with(other, isize::max)
+    }
+
+    pub fn manhattan_distance(&self, p: &Point<N>) -> usize {
+        self.0
+            .iter()
+            .zip(p.0.iter())
+            .map(|(a, b)| (a - b).unsigned_abs())
+            .sum()
+    }
+
+    /// The largest difference along any one axis: the number of king's moves between us.
+    pub fn chebyshev_distance(&self, p: &Point<N>) -> usize {
+        self.0
+            .iter()
+            .zip(p.0.iter())
+            .map(|(a, b)| (a - b).unsigned_abs())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// The square of the straight-line distance, which stays an integer.
+    pub fn squared_euclidean_distance(&self, p: &Point<N>) -> usize {
+        self.0
+            .iter()
+            .zip(p.0.iter())
+            .map(|(a, b)| (a - b).unsigned_abs().pow(2))
+            .sum()
+    }
+
+    /// The component-wise absolute value.
+    pub fn abs(&self) -> Point<N> {
+        self.map(isize::abs)
+    }
+
+    /// The component-wise sign (`-1`, `0`, or `1`).
+    pub fn signum(&self) -> Point<N> {
+        self.map(isize::signum)
+    }
+}
+
+impl<const N: usize> Coords<N> for Point<N> {
+    type Coord = isize;
+
+    fn coords(&self) -> [isize; N] {
+        self.0
+    }
+
+    fn from_coords(coords: [isize; N]) -> Self {
+        Point(coords)
+    }
+}
+
+impl<const N: usize> Index<usize> for Point<N> {
+    type Output = isize;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.0[index]
+    }
+}
+
+impl<const N: usize> IndexMut<usize> for Point<N> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.0[index]
+    }
+}
+
+impl<const N: usize> Add for Point<N> {
+    type Output = Point<N>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.zip_with(&rhs, |a, b| a + b)
+    }
+}
+
+impl<const N: usize> Sub for Point<N> {
+    type Output = Point<N>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.zip_with(&rhs, |a, b| a - b)
+    }
+}
+
+impl<const N: usize> Neg for Point<N> {
+    type Output = Point<N>;
+
+    fn neg(self) -> Self::Output {
+        self.map(|c| -c)
+    }
+}
+
+impl<const N: usize> Mul<isize> for Point<N> {
+    type Output = Point<N>;
+
+    fn mul(self, rhs: isize) -> Self::Output {
+        self.map(|c| c * rhs)
+    }
+}
+
+impl<const N: usize> AddAssign for Point<N> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<const N: usize> SubAssign for Point<N> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<const N: usize> Display for Point<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "(")?;
+        for (i, c) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{}", c)?;
+        }
+        write!(f, ")")
+    }
+}
+
+impl<const N: usize> FromStr for Point<N> {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let inner = s
+            .trim()
+            .strip_prefix('(')
+            .and_then(|s| s.strip_suffix(')'))
+            .ok_or_else(|| format!("Expected '(...)', but found '{}'", s))?;
+        let parts = inner.split(',').collect::<Vec<_>>();
+        if parts.len() != N {
+            return Err(format!(
+                "Expected {} components, but found {} in '{}'",
+                N,
+                parts.len(),
+                s
+            ));
+        }
+        let mut coords = [0; N];
+        for (c, p) in coords.iter_mut().zip(parts) {
+            *c = p
+                .trim()
+                .parse()
+                .map_err(|e| format!("Unparseable '{}' in '{}': {}", p, s, e))?;
+        }
+        Ok(Point(coords))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::geom::bounds::Bounds;
+    use crate::vector_type;
+    use std::collections::HashSet;
+
+    vector_type!(Vec2, i32, x, y);
+    vector_type!(Vec4, isize, x, y, z, w);
+
+    /// Written once, for any dimension.
+    fn live_neighbor_count<const N: usize>(live: &HashSet<Point<N>>, p: &Point<N>) -> usize {
+        p.moore_neighbors(1).filter(|n| live.contains(n)).count()
+    }
+
+    #[test]
+    fn dimension_agnostic() {
+        let live = [Point::new([0, 0]), Point::new([1, 1]), Point::new([5, 5])]
+            .iter()
+            .copied()
+            .collect::<HashSet<_>>();
+        assert_eq!(2, live_neighbor_count(&live, &Point::new([1, 0])));
+        let live = [Point::new([0, 0, 0]), Point::new([1, 1, 1])]
+            .iter()
+            .copied()
+            .collect::<HashSet<_>>();
+        assert_eq!(2, live_neighbor_count(&live, &Point::new([0, 1, 0])));
+        assert_eq!(80, Point::<4>::origin().moore_neighbors(1).count());
+        let b = Bounds::from_points(live.iter().copied()).unwrap();
+        assert_eq!(8, b.volume());
+    }
+
+    #[test]
+    fn ops() {
+        let a = Point::new([1, 2]);
+        let b = Point::new([3, -4]);
+        assert_eq!(Point::new([4, -2]), a + b);
+        assert_eq!(Point::new([-2, 6]), a - b);
+        assert_eq!(Point::new([-3, 4]), -b);
+        assert_eq!(Point::new([2, 4]), a * 2);
+        assert_eq!(Point::new([1, -4]), a.rectilinear_min(&b));
+        assert_eq!(Point::new([3, 2]), a.rectilinear_max(&b));
+        assert_eq!(Point::new([3, 4]), b.abs());
+        assert_eq!(Point::new([1, -1]), b.signum());
+        assert_eq!(8, a.manhattan_distance(&b));
+        let mut c = a;
+        c += b;
+        c -= a;
+        assert_eq!(b, c);
+        c[1] = 7;
+        assert_eq!(7, c[1]);
+    }
+
+    #[test]
+    fn parse() {
+        let p = Point::new([1, -22, 333]);
+        assert_eq!(Ok(p), p.to_string().parse());
+        assert!("(1,2)".parse::<Point<3>>().is_err());
+        assert!("(1,2,3,4)".parse::<Point<3>>().is_err());
+        assert!("1,2,3".parse::<Point<3>>().is_err());
+        assert!("(1,x,3)".parse::<Point<3>>().is_err());
+    }
+
+    #[test]
+    fn conversions() {
+        let v = Vec2::new(3, -1);
+        let p = Point::from(v);
+        assert_eq!(Point::new([3, -1]), p);
+        assert_eq!(v, Vec2::from(p));
+        let v = Vec4::new(1, 2, 3, 4);
+        assert_eq!(v, Point::from(v).into());
+    }
+}
@@ -0,0 +1,207 @@
+use crate::geom::Coords;
+use crate::histogram::Histogram;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+type Matrix = [[i8; 3]; 3];
+
+/// I am one of the 24 proper rotations of 3-D space which keep lattice points on the lattice: every
+/// way of pointing a cube's "forward" at one of its six faces, and then spinning it to one of four
+/// "ups." Reflections are not included.
+///
+/// # Examples
+///
+/// ```
+/// use aoc_2020::geom::rotation::Rotation;
+/// use aoc_2020::geom::point::Point;
+///
+/// assert_eq!(24, Rotation::all().count());
+/// let p = Point::new([1, 2, 3]);
+/// for r in Rotation::all() {
+///     assert_eq!(p, r.inverse().apply(&r.apply(&p)));
+/// }
+/// let r = Rotation::find(&[p], &[Point::new([-2, 1, 3])]).unwrap();
+/// assert_eq!(Point::new([-2, 1, 3]), r.apply(&p));
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Rotation {
+    m: Matrix,
+}
+
+impl Rotation {
+    pub fn identity() -> Rotation {
+        Rotation {
+            m: [[1, 0, 0], [0, 1, 0], [0, 0, 1]],
+        }
+    }
+
+    /// Iterate over all 24 rotations, starting with the identity.
+    pub fn all() -> impl Iterator<Item = Rotation> {
+        // every signed permutation matrix with a determinant of one
+        const PERMUTATIONS: [[usize; 3]; 6] = [
+            [0, 1, 2],
+            [0, 2, 1],
+            [1, 0, 2],
+            [1, 2, 0],
+            [2, 0, 1],
+            [2, 1, 0],
+        ];
+        PERMUTATIONS.iter().flat_map(|perm| {
+            (0..8).filter_map(move |signs: u8| {
+                let mut m = [[0; 3]; 3];
+                for (r, &c) in perm.iter().enumerate() {
+                    m[r][c] = if signs & (1 << r) == 0 { 1 } else { -1 };
+                }
+                let r = Rotation { m };
+                if r.determinant() == 1 {
+                    Some(r)
+                } else {
+                    None
+                }
+            })
+        })
+    }
+
+    fn determinant(&self) -> i8 {
+        let m = self.m;
+        m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+    }
+
+    /// The rotation equivalent to applying me, and then `next`.
+    pub fn then(&self, next: Rotation) -> Rotation {
+        let (a, b) = (next.m, self.m);
+        let mut m = [[0; 3]; 3];
+        for (r, row) in m.iter_mut().enumerate() {
+            for (c, cell) in row.iter_mut().enumerate() {
+                *cell = (0..3).map(|i| a[r][i] * b[i][c]).sum();
+            }
+        }
+        Rotation { m }
+    }
+
+    /// The rotation which undoes me.
+    pub fn inverse(&self) -> Rotation {
+        // rotation matrices are orthogonal, so the transpose is the inverse
+        let mut m = [[0; 3]; 3];
+        for (r, row) in m.iter_mut().enumerate() {
+            for (c, cell) in row.iter_mut().enumerate() {
+                *cell = self.m[c][r];
+            }
+        }
+        Rotation { m }
+    }
+
+    /// Rotate a point about the origin.
+    pub fn apply<P>(&self, p: &P) -> P
+    where
+        P: Coords<3>,
+    {
+        let coords = p.coords();
+        let mut result = coords;
+        for (r, out) in result.iter_mut().enumerate() {
+            // each row has exactly one nonzero entry
+            let c = (0..3).find(|&c| self.m[r][c] != 0).unwrap();
+            *out = if self.m[r][c] > 0 {
+                coords[c]
+            } else {
+                -coords[c]
+            };
+        }
+        P::from_coords(result)
+    }
+
+    /// Find a rotation which maps the `from` points exactly onto the `to` points, as sets.
+    pub fn find<P>(from: &[P], to: &[P]) -> Option<Rotation>
+    where
+        P: Coords<3> + Eq + Hash,
+    {
+        let to = to.iter().copied().collect::<HashSet<_>>();
+        Rotation::all().find(|r| {
+            let rotated = from.iter().map(|p| r.apply(p)).collect::<HashSet<_>>();
+            rotated == to
+        })
+    }
+
+    /// Find a rotation and translation which line up at least `min_overlap` of the `from` points
+    /// with the `to` points, when the former are rotated and then translated. The translation is
+    /// returned as a point, which is added to each rotated `from` point.
+    pub fn align<P>(from: &[P], to: &[P], min_overlap: usize) -> Option<(Rotation, P)>
+    where
+        P: Coords<3> + Eq + Hash,
+    {
+        for r in Rotation::all() {
+            let mut offsets = HashMap::new();
+            for f in from.iter().map(|p| r.apply(p)) {
+                let f = f.coords();
+                for t in to.iter().map(|p| p.coords()) {
+                    let offset = P::from_coords([t[0] - f[0], t[1] - f[1], t[2] - f[2]]);
+                    if offsets.increment_bucket(offset) >= min_overlap {
+                        return Some((r, offset));
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::geom::point::Point;
+    use crate::vector_type;
+
+    vector_type!(Vec3, i32, x, y, z);
+
+    #[test]
+    fn all_distinct_rotations() {
+        let all = Rotation::all().collect::<Vec<_>>();
+        assert_eq!(24, all.len());
+        assert_eq!(Rotation::identity(), all[0]);
+        assert_eq!(24, all.iter().collect::<HashSet<_>>().len());
+        // a point with distinct components has 24 distinct images
+        let p = Vec3::new(1, 2, 3);
+        assert_eq!(
+            24,
+            all.iter()
+                .map(|r| r.apply(&p))
+                .collect::<HashSet<_>>()
+                .len()
+        );
+    }
+
+    #[test]
+    fn group() {
+        for a in Rotation::all() {
+            assert_eq!(Rotation::identity(), a.then(a.inverse()));
+            for b in Rotation::all() {
+                let ab = a.then(b);
+                assert!(Rotation::all().any(|r| r == ab));
+                let p = Point::new([4, -5, 6]);
+                assert_eq!(b.apply(&a.apply(&p)), ab.apply(&p));
+            }
+        }
+    }
+
+    #[test]
+    fn find_and_align() {
+        let points = vec![
+            Vec3::new(1, 0, 0),
+            Vec3::new(0, 2, 0),
+            Vec3::new(0, 0, 3),
+            Vec3::new(-1, -1, 5),
+        ];
+        let r = Rotation::all().nth(13).unwrap();
+        let rotated = points.iter().map(|p| r.apply(p)).collect::<Vec<_>>();
+        assert_eq!(Some(r), Rotation::find(&points, &rotated));
+        assert_eq!(None, Rotation::find(&points, &rotated[1..]));
+
+        let offset = Vec3::new(10, -20, 30);
+        let mut moved = rotated.iter().map(|&p| p + offset).collect::<Vec<_>>();
+        moved.push(Vec3::new(100, 100, 100));
+        assert_eq!(Some((r, offset)), Rotation::align(&points, &moved, 4));
+        assert_eq!(None, Rotation::align(&points, &moved, 5));
+    }
+}
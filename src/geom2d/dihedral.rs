@@ -0,0 +1,222 @@
+use crate::geom::point::Point;
+use crate::geom::Coords;
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
+use Transform::*;
+
+/// I am one of the eight symmetries of a square: four rotations, and four reflections. Like grids
+/// in puzzle input, the y-axis points down, so a clockwise quarter turn takes East `(1,0)` to South
+/// `(0,1)`. Transforms are applied about the origin for points, and about the middle for grids.
+///
+/// # Examples
+///
+/// ```
+/// use aoc_2020::geom2d::dihedral::Transform;
+/// use strum::IntoEnumIterator;
+///
+/// let grid = vec![
+///     vec!['a', 'b', 'c'],
+///     vec!['d', 'e', 'f'],
+/// ];
+/// assert_eq!(
+///     vec![
+///         vec!['d', 'a'],
+///         vec!['e', 'b'],
+///         vec!['f', 'c'],
+///     ],
+///     Transform::Rotate90.apply_grid(&grid)
+/// );
+/// assert_eq!(Transform::Rotate270, Transform::Rotate90.inverse());
+/// assert_eq!(Transform::Rotate180, Transform::Rotate90.then(Transform::Rotate90));
+/// assert_eq!(8, Transform::iter().count());
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, EnumIter)]
+pub enum Transform {
+    Identity,
+    /// A clockwise quarter turn.
+    Rotate90,
+    Rotate180,
+    /// A counterclockwise quarter turn.
+    Rotate270,
+    /// Mirror left-to-right, across the y-axis.
+    FlipHorizontal,
+    /// Mirror top-to-bottom, across the x-axis.
+    FlipVertical,
+    /// Mirror across the main diagonal, swapping x and y.
+    Transpose,
+    /// Mirror across the anti-diagonal.
+    AntiTranspose,
+}
+
+type Matrix = [[isize; 2]; 2];
+
+impl Transform {
+    fn matrix(&self) -> Matrix {
+        match self {
+            Identity => [[1, 0], [0, 1]],
+            Rotate90 => [[0, -1], [1, 0]],
+            Rotate180 => [[-1, 0], [0, -1]],
+            Rotate270 => [[0, 1], [-1, 0]],
+            FlipHorizontal => [[-1, 0], [0, 1]],
+            FlipVertical => [[1, 0], [0, -1]],
+            Transpose => [[0, 1], [1, 0]],
+            AntiTranspose => [[0, -1], [-1, 0]],
+        }
+    }
+
+    fn from_matrix(m: Matrix) -> Transform {
+        Transform::iter()
+            .find(|t| t.matrix() == m)
+            .expect("Matrix isn't a symmetry of the square")
+    }
+
+    /// Whether I swap the x and y axes (and thus a grid's width and height).
+    pub fn swaps_axes(&self) -> bool {
+        self.matrix()[0][0] == 0
+    }
+
+    /// The transform equivalent to applying me, and then `next`.
+    pub fn then(&self, next: Transform) -> Transform {
+        let (a, b) = (next.matrix(), self.matrix());
+        let mut m = [[0; 2]; 2];
+        for (r, row) in m.iter_mut().enumerate() {
+            for (c, cell) in row.iter_mut().enumerate() {
+                *cell = a[r][0] * b[0][c] + a[r][1] * b[1][c];
+            }
+        }
+        Transform::from_matrix(m)
+    }
+
+    /// The transform which undoes me.
+    pub fn inverse(&self) -> Transform {
+        Transform::iter()
+            .find(|t| self.then(*t) == Identity)
+            .unwrap()
+    }
+
+    /// Transform a point about the origin.
+    pub fn apply<P>(&self, p: &P) -> P
+    where
+        P: Coords<2>,
+    {
+        let m = self.matrix();
+        let [x, y] = p.coords();
+        let coord = |n: isize| {
+            num_traits::cast::<_, P::Coord>(n).expect("Matrix entries are all -1, 0, or 1")
+        };
+        P::from_coords([
+            coord(m[0][0]) * x + coord(m[0][1]) * y,
+            coord(m[1][0]) * x + coord(m[1][1]) * y,
+        ])
+    }
+
+    /// Transform a rectangular grid, stored as a `Vec` of rows.
+    pub fn apply_grid<T>(&self, grid: &[Vec<T>]) -> Vec<Vec<T>>
+    where
+        T: Clone,
+    {
+        let height = grid.len();
+        let width = if height == 0 { 0 } else { grid[0].len() };
+        if width == 0 {
+            return Vec::new();
+        }
+        let a = self.apply(&Point::new([0, 0]));
+        let b = self.apply(&Point::new([width as isize - 1, height as isize - 1]));
+        let offset = a.rectilinear_min(&b);
+        let (new_width, new_height) = if self.swaps_axes() {
+            (height, width)
+        } else {
+            (width, height)
+        };
+        let inverse = self.inverse();
+        (0..new_height)
+            .map(|y| {
+                (0..new_width)
+                    .map(|x| {
+                        let src = inverse.apply(&(Point::new([x as isize, y as isize]) + offset));
+                        grid[src[1] as usize][src[0] as usize].clone()
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    vector_type!(Vec2, i32, x, y);
+
+    #[test]
+    fn points() {
+        let p = Point::new([2, 1]);
+        assert_eq!(Point::new([2, 1]), Identity.apply(&p));
+        assert_eq!(Point::new([-1, 2]), Rotate90.apply(&p));
+        assert_eq!(Point::new([-2, -1]), Rotate180.apply(&p));
+        assert_eq!(Point::new([1, -2]), Rotate270.apply(&p));
+        assert_eq!(Point::new([-2, 1]), FlipHorizontal.apply(&p));
+        assert_eq!(Point::new([2, -1]), FlipVertical.apply(&p));
+        assert_eq!(Point::new([1, 2]), Transpose.apply(&p));
+        assert_eq!(Point::new([-1, -2]), AntiTranspose.apply(&p));
+        assert_eq!(Vec2::new(0, 1), Rotate90.apply(&Vec2::new(1, 0)));
+    }
+
+    #[test]
+    fn group() {
+        for t in Transform::iter() {
+            assert_eq!(t, Identity.then(t));
+            assert_eq!(t, t.then(Identity));
+            assert_eq!(Identity, t.then(t.inverse()));
+            assert_eq!(Identity, t.inverse().then(t));
+            for u in Transform::iter() {
+                let p = Point::new([3, -7]);
+                assert_eq!(u.apply(&t.apply(&p)), t.then(u).apply(&p));
+            }
+        }
+        assert_eq!(
+            Identity,
+            Rotate90.then(Rotate90).then(Rotate90).then(Rotate90)
+        );
+        assert_eq!(Rotate270, FlipHorizontal.then(Transpose));
+        assert_eq!(Rotate90, Transpose.then(FlipHorizontal));
+    }
+
+    #[test]
+    fn grids() {
+        let grid = vec![vec![1, 2, 3], vec![4, 5, 6]];
+        assert_eq!(grid, Identity.apply_grid(&grid));
+        assert_eq!(
+            vec![vec![4, 1], vec![5, 2], vec![6, 3]],
+            Rotate90.apply_grid(&grid)
+        );
+        assert_eq!(
+            vec![vec![6, 5, 4], vec![3, 2, 1]],
+            Rotate180.apply_grid(&grid)
+        );
+        assert_eq!(
+            vec![vec![3, 6], vec![2, 5], vec![1, 4]],
+            Rotate270.apply_grid(&grid)
+        );
+        assert_eq!(
+            vec![vec![3, 2, 1], vec![6, 5, 4]],
+            FlipHorizontal.apply_grid(&grid)
+        );
+        assert_eq!(
+            vec![vec![4, 5, 6], vec![1, 2, 3]],
+            FlipVertical.apply_grid(&grid)
+        );
+        assert_eq!(
+            vec![vec![1, 4], vec![2, 5], vec![3, 6]],
+            Transpose.apply_grid(&grid)
+        );
+        assert_eq!(
+            vec![vec![6, 3], vec![5, 2], vec![4, 1]],
+            AntiTranspose.apply_grid(&grid)
+        );
+        for t in Transform::iter() {
+            assert_eq!(grid, t.inverse().apply_grid(&t.apply_grid(&grid)));
+        }
+        assert!(Rotate90.apply_grid::<u8>(&[]).is_empty());
+    }
+}
@@ -1,9 +1,37 @@
-/// Describes rectilinear directions on a coordinate plane.
-#[derive(Copy, Clone, Debug)]
+use crate::geom::point::Point;
+use crate::geom2d::dihedral::Transform;
+use std::convert::TryFrom;
+use std::str::FromStr;
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
+
+pub mod dihedral;
+pub mod path;
+
+/// Describes rectilinear directions on a coordinate plane. Like grids in puzzle input, the y-axis
+/// points down, so North's delta is `(0,-1)`. Iteration (via `strum`'s `IntoEnumIterator`) goes
+/// clockwise, starting from North.
+///
+/// # Examples
+///
+/// ```
+/// use aoc_2020::geom2d::{Dir, Turn};
+/// use aoc_2020::geom::point::Point;
+/// use std::convert::TryFrom;
+///
+/// let d = Dir::try_from('U').unwrap();
+/// assert_eq!(Dir::North, d);
+/// assert_eq!(Dir::West, d.turn(-1));
+/// assert_eq!(Dir::South, d.opposite());
+/// let t: Turn = "L270".parse().unwrap();
+/// assert_eq!(Dir::East, d.turn(t.quarter_turns()));
+/// assert_eq!(Point::new([0, -1]), d.delta());
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, EnumIter)]
 pub enum Dir {
     North,
-    South,
     East,
+    South,
     West,
 }
 
@@ -17,4 +45,319 @@ impl Dir {
             West => North,
         }
     }
+
+    pub fn counter_clockwise(&self) -> Dir {
+        self.turn(-1)
+    }
+
+    pub fn opposite(&self) -> Dir {
+        self.turn(2)
+    }
+
+    /// Turn by a number of quarter turns: clockwise if positive, counterclockwise if negative.
+    pub fn turn(&self, quarter_turns: isize) -> Dir {
+        (0..quarter_turns.rem_euclid(4)).fold(*self, |d, _| d.clockwise())
+    }
+
+    /// Turn by a number of degrees, clockwise if positive, which must be a multiple of 90.
+    pub fn turn_degrees(&self, degrees: isize) -> Result<Dir, String> {
+        Ok(self.turn(Turn::from_degrees(degrees)?.quarter_turns()))
+    }
+
+    /// The unit vector pointing my way.
+    pub fn delta(&self) -> Point<2> {
+        use Dir::*;
+        match self {
+            North => Point::new([0, -1]),
+            South => Point::new([0, 1]),
+            East => Point::new([1, 0]),
+            West => Point::new([-1, 0]),
+        }
+    }
+
+    /// Iterate over all four directions, clockwise from North.
+    pub fn all() -> impl Iterator<Item = Dir> {
+        Dir::iter()
+    }
+}
+
+/// Compass letters (`N`, `S`, `E`, `W`) or screen letters (`U`, `D`, `L`, `R`). Note that `L` and
+/// `R` are the absolute directions West and East here, not turns.
+impl TryFrom<char> for Dir {
+    type Error = String;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        use Dir::*;
+        match c {
+            'N' | 'U' => Ok(North),
+            'S' | 'D' => Ok(South),
+            'E' | 'R' => Ok(East),
+            'W' | 'L' => Ok(West),
+            _ => Err(format!("Unknown direction '{}'", c)),
+        }
+    }
+}
+
+impl FromStr for Dir {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.trim().chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Dir::try_from(c),
+            _ => Err(format!(
+                "Expected a single direction letter, but found '{}'",
+                s
+            )),
+        }
+    }
+}
+
+/// Describes the eight compass directions, diagonals included, with the same y-down convention as
+/// `Dir`. Iteration goes clockwise, starting from North, and turns are in 45° steps.
+///
+/// # Examples
+///
+/// ```
+/// use aoc_2020::geom2d::{Dir, Dir8};
+/// use aoc_2020::geom::point::Point;
+/// use std::convert::TryFrom;
+///
+/// assert_eq!(Dir8::NorthEast, Dir8::North.clockwise());
+/// assert_eq!(Point::new([-1, 1]), Dir8::SouthWest.delta());
+/// assert_eq!(Dir8::West, Dir8::from(Dir::West));
+/// assert_eq!(Ok(Dir::South), Dir::try_from(Dir8::South));
+/// assert!(Dir::try_from(Dir8::SouthEast).is_err());
+/// assert_eq!(8, Dir8::all().count());
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, EnumIter)]
+pub enum Dir8 {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Dir8 {
+    /// Turn 45° clockwise.
+    pub fn clockwise(&self) -> Dir8 {
+        self.turn(1)
+    }
+
+    /// Turn 45° counterclockwise.
+    pub fn counter_clockwise(&self) -> Dir8 {
+        self.turn(-1)
+    }
+
+    pub fn opposite(&self) -> Dir8 {
+        self.turn(4)
+    }
+
+    /// Turn by a number of eighth turns: clockwise if positive, counterclockwise if negative.
+    pub fn turn(&self, eighth_turns: isize) -> Dir8 {
+        // variants are declared in clockwise order
+        let i = (*self as isize + eighth_turns).rem_euclid(8);
+        Dir8::iter().nth(i as usize).unwrap()
+    }
+
+    pub fn is_diagonal(&self) -> bool {
+        (*self as usize) % 2 == 1
+    }
+
+    /// The vector one step my way, which is a unit vector for orthogonal directions, and has both
+    /// components nonzero for diagonals.
+    pub fn delta(&self) -> Point<2> {
+        use Dir8::*;
+        match self {
+            North => Point::new([0, -1]),
+            NorthEast => Point::new([1, -1]),
+            East => Point::new([1, 0]),
+            SouthEast => Point::new([1, 1]),
+            South => Point::new([0, 1]),
+            SouthWest => Point::new([-1, 1]),
+            West => Point::new([-1, 0]),
+            NorthWest => Point::new([-1, -1]),
+        }
+    }
+
+    /// Iterate over all eight directions, clockwise from North.
+    pub fn all() -> impl Iterator<Item = Dir8> {
+        Dir8::iter()
+    }
+}
+
+impl From<Dir> for Dir8 {
+    fn from(d: Dir) -> Self {
+        match d {
+            Dir::North => Dir8::North,
+            Dir::East => Dir8::East,
+            Dir::South => Dir8::South,
+            Dir::West => Dir8::West,
+        }
+    }
+}
+
+impl TryFrom<Dir8> for Dir {
+    type Error = String;
+
+    fn try_from(d: Dir8) -> Result<Self, Self::Error> {
+        match d {
+            Dir8::North => Ok(Dir::North),
+            Dir8::East => Ok(Dir::East),
+            Dir8::South => Ok(Dir::South),
+            Dir8::West => Ok(Dir::West),
+            _ => Err(format!("{:?} isn't a rectilinear direction", d)),
+        }
+    }
+}
+
+/// I am a relative turn, written like `R90` or `L270`, stored as a signed number of clockwise
+/// quarter turns.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Turn {
+    quarter_turns: isize,
+}
+
+impl Turn {
+    pub fn new(quarter_turns: isize) -> Turn {
+        Turn { quarter_turns }
+    }
+
+    /// Build a turn from a number of degrees clockwise, which must be a multiple of 90.
+    pub fn from_degrees(degrees: isize) -> Result<Turn, String> {
+        if degrees % 90 != 0 {
+            return Err(format!("Can't turn by {} degrees", degrees));
+        }
+        Ok(Turn::new(degrees / 90))
+    }
+
+    /// The number of clockwise quarter turns; negative for counterclockwise.
+    pub fn quarter_turns(&self) -> isize {
+        self.quarter_turns
+    }
+
+    pub fn degrees(&self) -> isize {
+        self.quarter_turns * 90
+    }
+
+    /// The rotation about the origin equivalent to me, for turning points rather than directions.
+    pub fn transform(&self) -> Transform {
+        match self.quarter_turns.rem_euclid(4) {
+            0 => Transform::Identity,
+            1 => Transform::Rotate90,
+            2 => Transform::Rotate180,
+            _ => Transform::Rotate270,
+        }
+    }
+}
+
+impl FromStr for Turn {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let sign = match s.chars().next() {
+            Some('R') => 1,
+            Some('L') => -1,
+            _ => return Err(format!("Expected 'L' or 'R' to start '{}'", s)),
+        };
+        let degrees = s[1..]
+            .parse::<isize>()
+            .map_err(|e| format!("Unparseable degrees in '{}': {}", s, e))?;
+        Turn::from_degrees(sign * degrees)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::geom::Coords;
+    use std::collections::HashSet;
+    use Dir::*;
+
+    #[test]
+    fn turns() {
+        for d in Dir::all() {
+            assert_eq!(d, d.clockwise().counter_clockwise());
+            assert_eq!(d, d.opposite().opposite());
+            assert_eq!(d.clockwise(), d.turn(1));
+            assert_eq!(d.clockwise(), d.turn(-3));
+            assert_eq!(d, d.turn(8));
+            assert_eq!(d.delta() * -1, d.opposite().delta());
+        }
+        assert_eq!(Ok(South), North.turn_degrees(180));
+        assert_eq!(Ok(North), East.turn_degrees(-90));
+        assert!(North.turn_degrees(45).is_err());
+    }
+
+    #[test]
+    fn deltas() {
+        assert_eq!(
+            vec![[0, -1], [1, 0], [0, 1], [-1, 0]],
+            Dir::all().map(|d| d.delta().coords()).collect::<Vec<_>>()
+        );
+        // a clockwise quarter turn of the delta matches a clockwise turn of the direction
+        for d in Dir::all() {
+            let [x, y] = d.delta().coords();
+            assert_eq!(Point::new([-y, x]), d.clockwise().delta());
+        }
+    }
+
+    #[test]
+    fn eight_way() {
+        for d in Dir8::all() {
+            assert_eq!(d, d.clockwise().counter_clockwise());
+            assert_eq!(d, d.turn(-8));
+            assert_eq!(d.opposite().delta(), d.delta() * -1);
+            assert_eq!(d.is_diagonal(), Dir::try_from(d).is_err());
+            assert_eq!(1, d.delta().chebyshev_distance(&Point::origin()));
+        }
+        for d in Dir::all() {
+            assert_eq!(d.delta(), Dir8::from(d).delta());
+            assert_eq!(Dir8::from(d.clockwise()), Dir8::from(d).turn(2));
+            assert_eq!(Ok(d), Dir::try_from(Dir8::from(d)));
+        }
+        assert_eq!(
+            8,
+            Dir8::all().map(|d| d.delta()).collect::<HashSet<_>>().len()
+        );
+        assert_eq!(Dir8::NorthWest, Dir8::North.counter_clockwise());
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!(
+            vec![North, South, East, West],
+            "NSEW"
+                .chars()
+                .map(|c| Dir::try_from(c).unwrap())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(Ok(South), "D".parse());
+        assert_eq!(Ok(West), " L ".parse());
+        assert!("X".parse::<Dir>().is_err());
+        assert!("NE".parse::<Dir>().is_err());
+        assert!("".parse::<Dir>().is_err());
+
+        assert_eq!(Ok(Turn::new(1)), "R90".parse());
+        assert_eq!(Ok(Turn::new(-3)), "L270".parse());
+        assert_eq!(-270, "L270".parse::<Turn>().unwrap().degrees());
+        assert!("R45".parse::<Turn>().is_err());
+        assert!("F90".parse::<Turn>().is_err());
+        assert!("Rx".parse::<Turn>().is_err());
+    }
+
+    #[test]
+    fn turn_transforms() {
+        for q in -5..=5 {
+            let t = Turn::new(q);
+            for d in Dir::all() {
+                assert_eq!(d.turn(q).delta(), t.transform().apply(&d.delta()));
+            }
+        }
+    }
 }
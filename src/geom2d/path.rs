@@ -0,0 +1,225 @@
+use crate::geom::point::Point;
+use crate::geom2d::Dir;
+use crate::histogram::Histogram;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+/// I trace a path across the lattice, one unit step at a time, remembering the step number at which
+/// each point was first reached (the start is step zero), and which points the path crossed back
+/// over.
+///
+/// # Examples
+///
+/// ```
+/// use aoc_2020::geom::point::Point;
+/// use aoc_2020::geom2d::path::Path;
+///
+/// let a: Path = "R8,U5,L5,D3".parse().unwrap();
+/// let b: Path = "U7,R6,D4,L4".parse().unwrap();
+/// assert_eq!(Some(12), a.first_visit(&Point::new([8, -4])));
+/// let crossings = a.intersections(&b);
+/// assert_eq!(2, crossings.len());
+/// assert_eq!(30, crossings[0].combined_steps());
+/// assert_eq!(
+///     Some(6),
+///     crossings.iter().map(|c| c.point.manhattan_distance(&Point::origin())).min()
+/// );
+/// ```
+#[derive(Clone, Debug)]
+pub struct Path {
+    position: Point<2>,
+    steps: usize,
+    first_visits: HashMap<Point<2>, usize>,
+    visit_counts: HashMap<Point<2>, usize>,
+    self_intersections: Vec<Point<2>>,
+}
+
+/// A point two paths have in common, with the step at which each first reached it.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Intersection {
+    pub point: Point<2>,
+    pub steps: (usize, usize),
+}
+
+impl Intersection {
+    pub fn combined_steps(&self) -> usize {
+        self.steps.0 + self.steps.1
+    }
+}
+
+impl Path {
+    pub fn new(start: Point<2>) -> Path {
+        let mut p = Path {
+            position: start,
+            steps: 0,
+            first_visits: HashMap::new(),
+            visit_counts: HashMap::new(),
+            self_intersections: Vec::new(),
+        };
+        p.visit();
+        p
+    }
+
+    /// Trace a sequence of moves from `start`.
+    pub fn trace<'a, I>(start: Point<2>, moves: I) -> Path
+    where
+        I: IntoIterator<Item = &'a (Dir, usize)>,
+    {
+        let mut p = Path::new(start);
+        for &(d, n) in moves {
+            p.walk(d, n);
+        }
+        p
+    }
+
+    /// Take `n` unit steps in direction `d`.
+    pub fn walk(&mut self, d: Dir, n: usize) {
+        for _ in 0..n {
+            self.position += d.delta();
+            self.steps += 1;
+            self.visit();
+        }
+    }
+
+    fn visit(&mut self) {
+        let (p, steps) = (self.position, self.steps);
+        self.first_visits.entry(p).or_insert(steps);
+        if self.visit_counts.increment_bucket(p) == 2 {
+            self.self_intersections.push(p);
+        }
+    }
+
+    /// Where the path currently ends.
+    pub fn end(&self) -> Point<2> {
+        self.position
+    }
+
+    /// The total number of steps taken.
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// The number of distinct points visited, including the start.
+    pub fn visited_count(&self) -> usize {
+        self.first_visits.len()
+    }
+
+    /// The step at which `p` was first reached, or `None` if it never was.
+    pub fn first_visit(&self, p: &Point<2>) -> Option<usize> {
+        self.first_visits.get(p).copied()
+    }
+
+    /// The points the path has returned to, in the order it first returned to them.
+    pub fn self_intersections(&self) -> &[Point<2>] {
+        &self.self_intersections
+    }
+
+    /// The points both paths visit, other than a start they share, ordered by combined steps (and
+    /// then by point, for stability).
+    pub fn intersections(&self, other: &Path) -> Vec<Intersection> {
+        let mut result = self
+            .first_visits
+            .iter()
+            .filter_map(|(&point, &a)| {
+                let b = other.first_visit(&point)?;
+                if a == 0 && b == 0 {
+                    None
+                } else {
+                    Some(Intersection {
+                        point,
+                        steps: (a, b),
+                    })
+                }
+            })
+            .collect::<Vec<_>>();
+        result.sort_by_key(|i| (i.combined_steps(), i.point));
+        result
+    }
+}
+
+/// I parse comma-separated moves like `R8,U5,L5,D3`, with any direction letter `Dir` accepts.
+pub fn parse_moves(s: &str) -> Result<Vec<(Dir, usize)>, String> {
+    s.trim()
+        .split(',')
+        .map(|m| {
+            let m = m.trim();
+            let c = m
+                .chars()
+                .next()
+                .ok_or_else(|| format!("Empty move in '{}'", s))?;
+            let n = m[c.len_utf8()..]
+                .parse()
+                .map_err(|e| format!("Unparseable '{}' in '{}': {}", m, s, e))?;
+            Ok((Dir::try_from(c)?, n))
+        })
+        .collect()
+}
+
+/// Parses moves (per `parse_moves`) and traces them from the origin.
+impl FromStr for Path {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Path::trace(Point::origin(), &parse_moves(s)?))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn visits() {
+        let p: Path = "R2,D2,L1,U3".parse().unwrap();
+        assert_eq!(8, p.steps());
+        assert_eq!(Point::new([1, -1]), p.end());
+        assert_eq!(8, p.visited_count());
+        assert_eq!(Some(0), p.first_visit(&Point::origin()));
+        assert_eq!(Some(2), p.first_visit(&Point::new([2, 0])));
+        assert_eq!(Some(6), p.first_visit(&Point::new([1, 1])));
+        // reached at step 1, then crossed again at step 7
+        assert_eq!(Some(1), p.first_visit(&Point::new([1, 0])));
+        assert_eq!(None, p.first_visit(&Point::new([0, 1])));
+        assert_eq!(&[Point::new([1, 0])], p.self_intersections());
+    }
+
+    #[test]
+    fn repeated_crossings_reported_once() {
+        let p: Path = "R1,L1,R1,L1".parse().unwrap();
+        assert_eq!(
+            vec![Point::origin(), Point::new([1, 0])],
+            p.self_intersections()
+        );
+        assert_eq!(2, p.visited_count());
+        assert!(Path::new(Point::origin()).self_intersections().is_empty());
+    }
+
+    #[test]
+    fn crossings() {
+        let a: Path = "R75,D30,R83,U83,L12,D49,R71,U7,L72".parse().unwrap();
+        let b: Path = "U62,R66,U55,R34,D71,R55,D58,R83".parse().unwrap();
+        let crossings = a.intersections(&b);
+        assert_eq!(610, crossings[0].combined_steps());
+        assert_eq!(
+            Some(159),
+            crossings
+                .iter()
+                .map(|c| c.point.manhattan_distance(&Point::origin()))
+                .min()
+        );
+        assert_eq!(crossings.len(), b.intersections(&a).len());
+        assert!(a.intersections(&"L5".parse().unwrap()).is_empty());
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!(
+            Ok(vec![(Dir::East, 8), (Dir::North, 5), (Dir::West, 12)]),
+            parse_moves("R8, U5,W12")
+        );
+        assert!(parse_moves("R8,,U5").is_err());
+        assert!(parse_moves("X8").is_err());
+        assert!(parse_moves("R-8").is_err());
+    }
+}
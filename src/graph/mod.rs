@@ -0,0 +1,271 @@
+use crate::indexer::Interner;
+use num_traits::PrimInt;
+use petgraph::graph::{DiGraph, NodeIndex};
+use petgraph::visit::{Dfs, EdgeRef, Reversed};
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// I collect labelled, weighted edges, interning the labels so each distinct one becomes a single
+/// node, and then build a `LabelledGraph` over a `petgraph` `DiGraph`.
+///
+/// # Examples
+///
+/// ```
+/// use aoc_2020::graph::GraphBuilder;
+///
+/// let mut builder = GraphBuilder::new();
+/// builder
+///     .add_edge("shiny gold", "dark olive", 1)
+///     .add_edge("shiny gold", "vibrant plum", 2)
+///     .add_edge("dark olive", "faded blue", 3)
+///     .add_edge("dark olive", "dotted black", 4)
+///     .add_edge("vibrant plum", "faded blue", 5)
+///     .add_edge("vibrant plum", "dotted black", 6)
+///     .add_edge("muted yellow", "shiny gold", 2);
+/// let graph = builder.build();
+/// assert_eq!(vec![&"muted yellow"], graph.ancestors(&"shiny gold"));
+/// assert_eq!(Some(32), graph.weighted_descendant_total(&"shiny gold"));
+/// ```
+pub struct GraphBuilder<L, W> {
+    labels: Interner<L>,
+    edges: Vec<(usize, usize, W)>,
+}
+
+impl<L, W> GraphBuilder<L, W>
+where
+    L: Hash + Eq + Clone,
+{
+    pub fn new() -> GraphBuilder<L, W> {
+        GraphBuilder {
+            labels: Interner::new(),
+            edges: Vec::new(),
+        }
+    }
+
+    /// Add a node, which is only needed for nodes without any edges.
+    pub fn add_node(&mut self, label: L) -> &mut Self {
+        self.labels.index_of(label);
+        self
+    }
+
+    /// Add an edge from one labelled node to another, adding either node if it's new.
+    pub fn add_edge(&mut self, from: L, to: L, weight: W) -> &mut Self {
+        let from = self.labels.index_of(from);
+        let to = self.labels.index_of(to);
+        self.edges.push((from, to, weight));
+        self
+    }
+
+    pub fn build(self) -> LabelledGraph<L, W> {
+        let mut graph = DiGraph::with_capacity(self.labels.len(), self.edges.len());
+        // added in index order, so node indices match interned indices
+        for label in self.labels.iter() {
+            graph.add_node(label.clone());
+        }
+        for (from, to, weight) in self.edges {
+            graph.add_edge(NodeIndex::new(from), NodeIndex::new(to), weight);
+        }
+        LabelledGraph {
+            labels: self.labels,
+            graph,
+        }
+    }
+}
+
+impl<L, W> Default for GraphBuilder<L, W>
+where
+    L: Hash + Eq + Clone,
+{
+    fn default() -> Self {
+        GraphBuilder::new()
+    }
+}
+
+/// I am a directed graph whose nodes are looked up by label. The underlying `petgraph` graph is
+/// available for any algorithms I don't wrap, with each node weighted by its label.
+pub struct LabelledGraph<L, W> {
+    labels: Interner<L>,
+    graph: DiGraph<L, W>,
+}
+
+impl<L, W> LabelledGraph<L, W>
+where
+    L: Hash + Eq + Clone,
+{
+    pub fn graph(&self) -> &DiGraph<L, W> {
+        &self.graph
+    }
+
+    /// The node for a label, or `None` if there's no such node.
+    pub fn node<Q>(&self, label: &Q) -> Option<NodeIndex>
+    where
+        L: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.labels.get(label).map(NodeIndex::new)
+    }
+
+    pub fn label(&self, node: NodeIndex) -> &L {
+        &self.graph[node]
+    }
+
+    /// The labels of every node with a path to `label`'s node, in insertion order. Unknown labels
+    /// have no ancestors.
+    pub fn ancestors<Q>(&self, label: &Q) -> Vec<&L>
+    where
+        L: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let start = match self.node(label) {
+            Some(n) => n,
+            None => return Vec::new(),
+        };
+        let mut dfs = Dfs::new(Reversed(&self.graph), start);
+        let mut nodes = Vec::new();
+        while let Some(n) = dfs.next(Reversed(&self.graph)) {
+            nodes.push(n);
+        }
+        self.labels_except(nodes, start)
+    }
+
+    /// The labels of every node with a path from `label`'s node, in insertion order. Unknown labels
+    /// have no descendants.
+    pub fn descendants<Q>(&self, label: &Q) -> Vec<&L>
+    where
+        L: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let start = match self.node(label) {
+            Some(n) => n,
+            None => return Vec::new(),
+        };
+        let mut dfs = Dfs::new(&self.graph, start);
+        let mut nodes = Vec::new();
+        while let Some(n) = dfs.next(&self.graph) {
+            nodes.push(n);
+        }
+        self.labels_except(nodes, start)
+    }
+
+    /// The labels of the nodes other than `start`, in insertion order.
+    fn labels_except(&self, mut nodes: Vec<NodeIndex>, start: NodeIndex) -> Vec<&L> {
+        nodes.retain(|&n| n != start);
+        nodes.sort();
+        nodes.into_iter().map(|n| self.label(n)).collect()
+    }
+}
+
+impl<L, W> LabelledGraph<L, W>
+where
+    L: Hash + Eq + Clone,
+    W: PrimInt,
+{
+    /// The total weight below `label`'s node, where each edge contributes its weight times one
+    /// more than the total below its target: how many bags a bag must contain, say. `None` if the
+    /// label is unknown, a cycle is reachable (making the total infinite), or the total overflows.
+    pub fn weighted_descendant_total<Q>(&self, label: &Q) -> Option<W>
+    where
+        L: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let start = self.node(label)?;
+        self.total_below(start, &mut HashMap::new())
+    }
+
+    /// Memoized, with `None` marking nodes in progress, so reaching one again means a cycle.
+    fn total_below(
+        &self,
+        node: NodeIndex,
+        totals: &mut HashMap<NodeIndex, Option<W>>,
+    ) -> Option<W> {
+        if let Some(&total) = totals.get(&node) {
+            return total;
+        }
+        totals.insert(node, None);
+        let mut total = W::zero();
+        for e in self.graph.edges(node) {
+            let below = self.total_below(e.target(), totals)?;
+            let each = below.checked_add(&W::one())?;
+            total = total.checked_add(&e.weight().checked_mul(&each)?)?;
+        }
+        totals.insert(node, Some(total));
+        Some(total)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const BAG_RULES: &[(&str, &str, u32)] = &[
+        ("light red", "bright white", 1),
+        ("light red", "muted yellow", 2),
+        ("dark orange", "bright white", 3),
+        ("dark orange", "muted yellow", 4),
+        ("bright white", "shiny gold", 1),
+        ("muted yellow", "shiny gold", 2),
+        ("muted yellow", "faded blue", 9),
+        ("shiny gold", "dark olive", 1),
+        ("shiny gold", "vibrant plum", 2),
+        ("dark olive", "faded blue", 3),
+        ("dark olive", "dotted black", 4),
+        ("vibrant plum", "faded blue", 5),
+        ("vibrant plum", "dotted black", 6),
+    ];
+
+    fn bag_graph() -> LabelledGraph<String, u32> {
+        let mut builder = GraphBuilder::new();
+        for &(from, to, n) in BAG_RULES {
+            builder.add_edge(from.to_string(), to.to_string(), n);
+        }
+        builder.build()
+    }
+
+    #[test]
+    fn structure() {
+        let g = bag_graph();
+        assert_eq!(9, g.graph().node_count());
+        assert_eq!(BAG_RULES.len(), g.graph().edge_count());
+        let gold = g.node("shiny gold").unwrap();
+        assert_eq!("shiny gold", g.label(gold));
+        assert_eq!(None, g.node("plaid"));
+    }
+
+    #[test]
+    fn ancestors_and_descendants() {
+        let g = bag_graph();
+        assert_eq!(
+            vec!["light red", "bright white", "muted yellow", "dark orange"],
+            g.ancestors("shiny gold")
+        );
+        assert_eq!(
+            vec!["faded blue", "dark olive", "vibrant plum", "dotted black"],
+            g.descendants("shiny gold")
+        );
+        assert!(g.ancestors("light red").is_empty());
+        assert!(g.descendants("faded blue").is_empty());
+        assert!(g.ancestors("plaid").is_empty());
+    }
+
+    #[test]
+    fn weighted_totals() {
+        let g = bag_graph();
+        assert_eq!(Some(32), g.weighted_descendant_total("shiny gold"));
+        assert_eq!(Some(0), g.weighted_descendant_total("faded blue"));
+        assert_eq!(None, g.weighted_descendant_total("plaid"));
+
+        let mut builder = GraphBuilder::new();
+        builder
+            .add_edge('a', 'b', 2u8)
+            .add_edge('b', 'a', 1)
+            .add_edge('c', 'd', 200)
+            .add_edge('d', 'e', 2)
+            .add_node('z');
+        let g = builder.build();
+        assert_eq!(None, g.weighted_descendant_total(&'a'));
+        assert_eq!(None, g.weighted_descendant_total(&'c'));
+        assert_eq!(Some(2), g.weighted_descendant_total(&'d'));
+        assert_eq!(Some(0), g.weighted_descendant_total(&'z'));
+    }
+}
@@ -0,0 +1,268 @@
+use crate::ascii::chart::AsciiChart;
+use num_traits::ToPrimitive;
+use std::ops::Range;
+
+/// The most bins a fixed-width `BinnedHistogram` grows to span.
+pub const MAX_BINS: usize = 1 << 16;
+
+/// I am a histogram for continuous values, which I count into equal-width bins. Either the bin
+/// width is fixed, and bins are added as needed to cover whatever values arrive, or the number of
+/// bins over a fixed range is, and values outside that range are only tallied as out of range.
+/// Bins include their lower bound but not their upper bound, except that the last bin of a fixed
+/// range includes the range's maximum. With a fixed width, I never span more than `MAX_BINS` bins,
+/// and values which would stretch me further are tallied as out of range too.
+///
+/// # Examples
+///
+/// ```
+/// use aoc_2020::ascii::chart::AsciiChart;
+/// use aoc_2020::histogram::binned::BinnedHistogram;
+///
+/// let mut hist = BinnedHistogram::with_bin_count(0.0, 10.0, 4);
+/// hist.add_all(vec![0.5, 2.5, 3.0, 9.9, 10.0, 12.0]);
+/// assert_eq!(vec![1, 2, 0, 2], hist.counts());
+/// assert_eq!(1, hist.out_of_range());
+/// assert_eq!("[7.5, 10]", hist.bin_label(3));
+///
+/// let mut hist = BinnedHistogram::with_bin_width(0.0, 5.0);
+/// hist.add_all(vec![12, -3, 14, 0]);
+/// assert_eq!(vec![1, 1, 0, 2], hist.counts());
+/// assert_eq!(-5.0..0.0, hist.bin_range(0));
+/// assert!(hist.render_histogram().contains("[10, 15) |"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct BinnedHistogram {
+    origin: f64,
+    width: f64,
+    /// The index, relative to `origin`, of the first bin in `counts`.
+    first: i64,
+    counts: Vec<usize>,
+    /// The fixed range's maximum, if there is one.
+    max: Option<f64>,
+    out_of_range: usize,
+}
+
+impl BinnedHistogram {
+    /// Bins of the given width, aligned so one starts at `origin`, created as needed.
+    pub fn with_bin_width(origin: f64, width: f64) -> BinnedHistogram {
+        assert!(width > 0.0, "Bin width must be positive");
+        BinnedHistogram {
+            origin,
+            width,
+            first: 0,
+            counts: Vec::new(),
+            max: None,
+            out_of_range: 0,
+        }
+    }
+
+    /// `count` equal bins spanning `min` to `max`, inclusive.
+    pub fn with_bin_count(min: f64, max: f64, count: usize) -> BinnedHistogram {
+        assert!(count > 0, "Need at least one bin");
+        assert!(max > min, "Range must be nonempty");
+        BinnedHistogram {
+            counts: vec![0; count],
+            max: Some(max),
+            ..BinnedHistogram::with_bin_width(min, (max - min) / count as f64)
+        }
+    }
+
+    /// Add a value, returning whether it landed in a bin. Values which can't be represented as an
+    /// `f64`, NaN, those outside a fixed range, those too far from the origin for an `i64` to
+    /// number their bin, and those too far from the existing bins to span with `MAX_BINS` are
+    /// tallied as out of range.
+    pub fn add<N>(&mut self, value: N) -> bool
+    where
+        N: ToPrimitive,
+    {
+        let v = match value.to_f64() {
+            Some(v) if v.is_finite() => v,
+            _ => {
+                self.out_of_range += 1;
+                return false;
+            }
+        };
+        let bin = ((v - self.origin) / self.width).floor();
+        // `as` would saturate, putting the value in a bin which doesn't contain it
+        if !(bin >= i64::MIN as f64 && bin < i64::MAX as f64) {
+            self.out_of_range += 1;
+            return false;
+        }
+        let mut bin = bin as i64;
+        if let Some(max) = self.max {
+            if v < self.origin || v > max {
+                self.out_of_range += 1;
+                return false;
+            }
+            // the maximum itself goes in the last bin
+            bin = bin.min(self.counts.len() as i64 - 1);
+        } else if self.counts.is_empty() {
+            self.first = bin;
+            self.counts.push(0);
+        } else if !self.can_span(bin) {
+            self.out_of_range += 1;
+            return false;
+        } else if bin < self.first {
+            let mut counts = vec![0; (self.first - bin) as usize];
+            counts.append(&mut self.counts);
+            self.counts = counts;
+            self.first = bin;
+        } else if bin >= self.first + self.counts.len() as i64 {
+            self.counts.resize((bin - self.first) as usize + 1, 0);
+        }
+        self.counts[(bin - self.first) as usize] += 1;
+        true
+    }
+
+    /// Whether the bins could stretch to include `bin` without spanning more than `MAX_BINS`.
+    fn can_span(&self, bin: i64) -> bool {
+        let last = self.first + self.counts.len() as i64 - 1;
+        match bin.max(last).checked_sub(bin.min(self.first)) {
+            Some(span) => span < MAX_BINS as i64,
+            None => false,
+        }
+    }
+
+    pub fn add_all<I, N>(&mut self, values: I)
+    where
+        I: IntoIterator<Item = N>,
+        N: ToPrimitive,
+    {
+        for v in values {
+            self.add(v);
+        }
+    }
+
+    /// The count in each bin, lowest first.
+    pub fn counts(&self) -> Vec<usize> {
+        self.counts.clone()
+    }
+
+    pub fn bin_count(&self) -> usize {
+        self.counts.len()
+    }
+
+    /// The number of values which landed in a bin.
+    pub fn total(&self) -> usize {
+        self.counts.iter().sum()
+    }
+
+    /// The number of values which didn't land in a bin.
+    pub fn out_of_range(&self) -> usize {
+        self.out_of_range
+    }
+
+    /// The range of values in the `i`th bin.
+    pub fn bin_range(&self, i: usize) -> Range<f64> {
+        let lo = self.origin + (self.first + i as i64) as f64 * self.width;
+        match self.max {
+            Some(max) if i + 1 == self.counts.len() => lo..max,
+            _ => lo..(lo + self.width),
+        }
+    }
+
+    /// The `i`th bin's range, written in interval notation, like `[2.5, 5)`.
+    pub fn bin_label(&self, i: usize) -> String {
+        let r = self.bin_range(i);
+        let closed = self.max.is_some() && i + 1 == self.counts.len();
+        format!("[{}, {}{}", r.start, r.end, if closed { ']' } else { ')' })
+    }
+
+    /// Each bin's range and count, lowest first.
+    pub fn bins(&self) -> Vec<(Range<f64>, usize)> {
+        self.counts
+            .iter()
+            .enumerate()
+            .map(|(i, &c)| (self.bin_range(i), c))
+            .collect()
+    }
+}
+
+impl AsciiChart<usize> for BinnedHistogram {
+    fn to_chart_tuples(&self) -> Vec<(String, usize)> {
+        self.counts
+            .iter()
+            .enumerate()
+            .map(|(i, &c)| (self.bin_label(i), c))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn fixed_width_grows_both_ways() {
+        let mut hist = BinnedHistogram::with_bin_width(1.0, 0.5);
+        assert_eq!(0, hist.bin_count());
+        assert!(hist.add(2.2));
+        assert_eq!(vec![1], hist.counts());
+        assert_eq!(2.0..2.5, hist.bin_range(0));
+        assert!(hist.add(0));
+        assert!(hist.add(3u8));
+        assert!(hist.add(2.0));
+        assert_eq!(vec![1, 0, 0, 0, 2, 0, 1], hist.counts());
+        assert_eq!("[0, 0.5)", hist.bin_label(0));
+        assert_eq!("[3, 3.5)", hist.bin_label(6));
+        assert!(!hist.add(f64::NAN));
+        assert!(!hist.add(f64::INFINITY));
+        assert_eq!(4, hist.total());
+        assert_eq!(2, hist.out_of_range());
+    }
+
+    #[test]
+    fn fixed_width_span_is_capped() {
+        let mut hist = BinnedHistogram::with_bin_width(0.0, 1.0);
+        assert!(hist.add(0.0));
+        assert!(!hist.add(1e30));
+        assert!(!hist.add(-1e30));
+        assert!(!hist.add(MAX_BINS as f64));
+        assert!(hist.add(MAX_BINS as f64 - 1.0));
+        assert_eq!(MAX_BINS, hist.bin_count());
+        assert!(!hist.add(-1.0));
+        assert_eq!(2, hist.total());
+        assert_eq!(4, hist.out_of_range());
+        let mut hist = BinnedHistogram::with_bin_width(0.0, 1.0);
+        assert!(!hist.add(-1e30));
+        assert!(!hist.add(1e30));
+        assert!(!hist.add(f64::MAX));
+        assert_eq!(0, hist.bin_count());
+        assert_eq!(3, hist.out_of_range());
+    }
+
+    #[test]
+    fn fixed_count() {
+        let mut hist = BinnedHistogram::with_bin_count(-1.0, 1.0, 4);
+        hist.add_all(vec![-1.0, -0.5, -0.25, 0.0, 0.99, 1.0, 1.01, -1.5]);
+        assert_eq!(vec![1, 2, 1, 2], hist.counts());
+        assert_eq!(2, hist.out_of_range());
+        assert_eq!(
+            vec![
+                (-1.0..-0.5, 1),
+                (-0.5..0.0, 2),
+                (0.0..0.5, 1),
+                (0.5..1.0, 2)
+            ],
+            hist.bins()
+        );
+        assert_eq!("[-1, -0.5)", hist.bin_label(0));
+        assert_eq!("[0.5, 1]", hist.bin_label(3));
+    }
+
+    #[test]
+    fn chart() {
+        let mut hist = BinnedHistogram::with_bin_count(0.0, 30.0, 3);
+        hist.add_all(vec![1, 2, 3, 15, 25, 26]);
+        assert_eq!(
+            vec![
+                ("[0, 10)".to_string(), 3),
+                ("[10, 20)".to_string(), 1),
+                ("[20, 30]".to_string(), 2)
+            ],
+            hist.to_chart_tuples()
+        );
+        let chart = hist.render_histogram();
+        assert_eq!(4, chart.lines().count());
+    }
+}
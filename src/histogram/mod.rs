@@ -1,5 +1,9 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::hash::Hash;
+use std::iter::FromIterator;
+
+pub mod binned;
+pub mod stats;
 
 /// Histograms are a set of buckets with a count of how many items are in the bucket. The buckets
 /// are usually a set of discrete segments which cover a continuous interval, but don't have to be.
@@ -12,6 +16,9 @@ use std::hash::Hash;
 /// 1.  `HashMap<T, usize>` which allows arbitrary `Eq + Hash` bucket labels, and is better suited
 ///     to sparsely filled buckets or those which don't naturally map onto `usize`.
 ///
+/// There are also `BTreeMap<T, usize>`, for `Ord` labels which should iterate in order, and
+/// `[usize; N]`, for a known, small number of `usize` labels without any allocation.
+///
 /// # Examples
 ///
 /// Ages of people at a kid's birthday party, using `Vec` w/ `usize` as the bucket type:
@@ -83,6 +90,323 @@ pub trait Histogram<T> {
 
     /// Retrieve the value of a bucket.
     fn get_bucket(&self, bucket: &T) -> usize;
+
+    /// Set a bucket to an arbitrary count. Setting it to zero empties it.
+    fn set_bucket(&mut self, bucket: T, count: usize);
+
+    /// Decrement a bucket by one, stopping at zero, and return the new value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use aoc_2020::histogram::Histogram;
+    /// use std::collections::HashMap;
+    ///
+    /// // the number of distinct values in each window of three
+    /// let nums = [1, 2, 1, 1, 3, 4];
+    /// let mut window = HashMap::new();
+    /// let mut distinct = vec![];
+    /// for (i, &n) in nums.iter().enumerate() {
+    ///     window.increment_bucket(n);
+    ///     if i >= 3 {
+    ///         window.decrement_bucket(nums[i - 3]);
+    ///     }
+    ///     if i >= 2 {
+    ///         distinct.push(window.len());
+    ///     }
+    /// }
+    /// assert_eq!(vec![2, 2, 2, 3], distinct);
+    /// ```
+    fn decrement_bucket(&mut self, bucket: T) -> usize {
+        self.decrement_bucket_by(bucket, 1)
+    }
+
+    /// Decrement a bucket by an arbitrary step, stopping at zero, and return the new value.
+    fn decrement_bucket_by(&mut self, bucket: T, step: usize) -> usize {
+        let count = self.get_bucket(&bucket).saturating_sub(step);
+        self.set_bucket(bucket, count);
+        count
+    }
+
+    /// Empty a bucket, returning the count it had.
+    fn remove_bucket(&mut self, bucket: T) -> usize {
+        let count = self.get_bucket(&bucket);
+        self.set_bucket(bucket, 0);
+        count
+    }
+
+    /// The sum of all buckets' counts.
+    fn total(&self) -> usize;
+
+    /// Every bucket with a nonzero count, along with that count, in no particular order.
+    fn nonzero_buckets(&self) -> Vec<(T, usize)>
+    where
+        T: Clone;
+
+    /// Up to `n` nonzero buckets with the highest counts, highest first. Ties are broken by label,
+    /// lowest first, so the result is stable.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use aoc_2020::histogram::Histogram;
+    /// use std::collections::HashMap;
+    ///
+    /// let mut hist = HashMap::new();
+    /// for c in "mississippi".chars() {
+    ///     hist.increment_bucket(c);
+    /// }
+    /// assert_eq!(11, hist.total());
+    /// assert_eq!(vec![('i', 4), ('s', 4)], hist.most_common(2));
+    /// assert_eq!(vec![('m', 1), ('p', 2)], hist.least_common(2));
+    /// assert_eq!(Some(('i', 4)), hist.max_bucket());
+    /// ```
+    fn most_common(&self, n: usize) -> Vec<(T, usize)>
+    where
+        T: Ord + Clone,
+    {
+        let mut buckets = self.sorted_by_count();
+        buckets.truncate(n);
+        buckets
+    }
+
+    /// Up to `n` nonzero buckets with the lowest counts, lowest first. Ties are broken by label,
+    /// lowest first. Empty buckets are never included.
+    fn least_common(&self, n: usize) -> Vec<(T, usize)>
+    where
+        T: Ord + Clone,
+    {
+        let mut buckets = self.nonzero_buckets();
+        buckets.sort_by(|(a, ac), (b, bc)| ac.cmp(bc).then_with(|| a.cmp(b)));
+        buckets.truncate(n);
+        buckets
+    }
+
+    /// The bucket with the highest count (the mode), or `None` if every bucket is empty. Ties go
+    /// to the lowest label.
+    fn max_bucket(&self) -> Option<(T, usize)>
+    where
+        T: Ord + Clone,
+    {
+        self.most_common(1).pop()
+    }
+
+    /// Every nonzero bucket, highest count first, with ties broken by label, lowest first.
+    fn sorted_by_count(&self) -> Vec<(T, usize)>
+    where
+        T: Ord + Clone,
+    {
+        let mut buckets = self.nonzero_buckets();
+        buckets.sort_by(|(a, ac), (b, bc)| bc.cmp(ac).then_with(|| a.cmp(b)));
+        buckets
+    }
+
+    /// Every nonzero bucket, in label order.
+    fn sorted_by_label(&self) -> Vec<(T, usize)>
+    where
+        T: Ord + Clone,
+    {
+        let mut buckets = self.nonzero_buckets();
+        buckets.sort_by(|(a, _), (b, _)| a.cmp(b));
+        buckets
+    }
+
+    /// The Shannon entropy of the buckets' proportions, in bits, or `None` if every bucket is
+    /// empty. It's zero if every count is in one bucket, and largest when the counts are spread
+    /// evenly. Only the counts matter, so the labels can be anything.
+    fn entropy(&self) -> Option<f64>
+    where
+        T: Clone,
+    {
+        let n = match self.total() {
+            0 => return None,
+            n => n as f64,
+        };
+        Some(
+            -self
+                .nonzero_buckets()
+                .into_iter()
+                .map(|(_, c)| c as f64 / n)
+                .map(|p| p * p.log2())
+                .sum::<f64>(),
+        )
+    }
+
+    /// Add each of `other`'s buckets to mine: a multiset sum.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use aoc_2020::histogram::{Histogram, IntoHistogram};
+    ///
+    /// let mut have = "aabbbc".chars().histogram();
+    /// let need = "abbbbd".chars().histogram();
+    /// let mut short = need.clone();
+    /// short.subtract(&have);
+    /// assert_eq!(vec![('b', 1), ('d', 1)], short.sorted_by_label());
+    /// let mut common = have.clone();
+    /// common.intersect(&need);
+    /// assert_eq!(vec![('a', 1), ('b', 3)], common.sorted_by_label());
+    /// have.union(&need);
+    /// assert_eq!(vec![('a', 2), ('b', 4), ('c', 1), ('d', 1)], have.sorted_by_label());
+    /// have.merge(&need);
+    /// assert_eq!(14, have.total());
+    /// ```
+    fn merge<H>(&mut self, other: &H)
+    where
+        H: Histogram<T> + ?Sized,
+        T: Clone,
+    {
+        for (b, c) in other.nonzero_buckets() {
+            self.increment_bucket_by(b, c);
+        }
+    }
+
+    /// Take each of `other`'s buckets away from mine, stopping at zero: a multiset difference.
+    fn subtract<H>(&mut self, other: &H)
+    where
+        H: Histogram<T> + ?Sized,
+        T: Clone,
+    {
+        for (b, c) in other.nonzero_buckets() {
+            let count = self.get_bucket(&b).saturating_sub(c);
+            self.set_bucket(b, count);
+        }
+    }
+
+    /// Keep the smaller of my and `other`'s count for each bucket: a multiset intersection.
+    fn intersect<H>(&mut self, other: &H)
+    where
+        H: Histogram<T> + ?Sized,
+        T: Clone,
+    {
+        for (b, c) in self.nonzero_buckets() {
+            let count = c.min(other.get_bucket(&b));
+            self.set_bucket(b, count);
+        }
+    }
+
+    /// Keep the larger of my and `other`'s count for each bucket: a multiset union.
+    fn union<H>(&mut self, other: &H)
+    where
+        H: Histogram<T> + ?Sized,
+        T: Clone,
+    {
+        for (b, c) in other.nonzero_buckets() {
+            if c > self.get_bucket(&b) {
+                self.set_bucket(b, c);
+            }
+        }
+    }
+}
+
+/// I let any iterator count its items into a histogram in one call.
+///
+/// # Example
+///
+/// ```
+/// use aoc_2020::histogram::{Counts, Histogram, IntoHistogram};
+///
+/// let hist = "hello world".chars().filter(|c| c.is_alphabetic()).histogram();
+/// assert_eq!(3, hist.get_bucket(&'l'));
+/// let words = "the quick brown fox jumps over the lazy dog".split(' ');
+/// let lengths = words.map(|w| w.len()).collect::<Counts<_>>();
+/// assert_eq!(Some((3, 4)), lengths.max_bucket());
+/// ```
+pub trait IntoHistogram: Iterator {
+    fn histogram(self) -> HashMap<Self::Item, usize>
+    where
+        Self: Sized,
+        Self::Item: Eq + Hash,
+    {
+        let mut hist = HashMap::new();
+        for item in self {
+            hist.increment_bucket(item);
+        }
+        hist
+    }
+}
+
+impl<I> IntoHistogram for I where I: Iterator {}
+
+/// I am a `HashMap`-backed histogram which can be built with `collect`. I never store empty
+/// buckets, so equal histograms compare equal.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Counts<T>
+where
+    T: Eq + Hash,
+{
+    buckets: HashMap<T, usize>,
+}
+
+impl<T> Counts<T>
+where
+    T: Eq + Hash,
+{
+    pub fn new() -> Counts<T> {
+        Counts {
+            buckets: HashMap::new(),
+        }
+    }
+
+    pub fn as_map(&self) -> &HashMap<T, usize> {
+        &self.buckets
+    }
+
+    pub fn into_map(self) -> HashMap<T, usize> {
+        self.buckets
+    }
+}
+
+impl<T> Default for Counts<T>
+where
+    T: Eq + Hash,
+{
+    fn default() -> Self {
+        Counts::new()
+    }
+}
+
+impl<T> Histogram<T> for Counts<T>
+where
+    T: Eq + Hash,
+{
+    fn increment_bucket_by(&mut self, bucket: T, step: usize) -> usize {
+        if step == 0 {
+            return self.get_bucket(&bucket);
+        }
+        self.buckets.increment_bucket_by(bucket, step)
+    }
+
+    fn get_bucket(&self, bucket: &T) -> usize {
+        self.buckets.get_bucket(bucket)
+    }
+
+    fn set_bucket(&mut self, bucket: T, count: usize) {
+        self.buckets.set_bucket(bucket, count)
+    }
+
+    fn total(&self) -> usize {
+        self.buckets.total()
+    }
+
+    fn nonzero_buckets(&self) -> Vec<(T, usize)>
+    where
+        T: Clone,
+    {
+        self.buckets.nonzero_buckets()
+    }
+}
+
+impl<T> FromIterator<T> for Counts<T>
+where
+    T: Eq + Hash,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Counts {
+            buckets: iter.into_iter().histogram(),
+        }
+    }
 }
 
 impl Histogram<usize> for Vec<usize> {
@@ -106,6 +430,30 @@ impl Histogram<usize> for Vec<usize> {
             None => 0,
         }
     }
+
+    /// Set a bucket's count, creating it (and all lower-numbered buckets) if needed, unless the
+    /// count is zero.
+    fn set_bucket(&mut self, bucket: usize, count: usize) {
+        if bucket < self.len() {
+            self[bucket] = count;
+        } else if count > 0 {
+            self.resize(bucket, 0);
+            self.push(count);
+        }
+    }
+
+    fn total(&self) -> usize {
+        self.iter().sum()
+    }
+
+    /// Nonzero buckets, in label order.
+    fn nonzero_buckets(&self) -> Vec<(usize, usize)> {
+        self.iter()
+            .enumerate()
+            .filter(|(_, &c)| c > 0)
+            .map(|(b, &c)| (b, c))
+            .collect()
+    }
 }
 
 impl<T> Histogram<T> for HashMap<T, usize>
@@ -126,6 +474,109 @@ where
             None => 0,
         }
     }
+
+    /// Set a bucket's count, removing it if the count is zero.
+    fn set_bucket(&mut self, bucket: T, count: usize) {
+        if count == 0 {
+            self.remove(&bucket);
+        } else {
+            self.insert(bucket, count);
+        }
+    }
+
+    fn total(&self) -> usize {
+        self.values().sum()
+    }
+
+    fn nonzero_buckets(&self) -> Vec<(T, usize)>
+    where
+        T: Clone,
+    {
+        self.iter()
+            .filter(|(_, &c)| c > 0)
+            .map(|(b, &c)| (b.clone(), c))
+            .collect()
+    }
+}
+
+impl<T> Histogram<T> for BTreeMap<T, usize>
+where
+    T: Ord,
+{
+    /// Increment a bucket by the given amount, inserting it - and only it - if needed.
+    fn increment_bucket_by(&mut self, bucket: T, step: usize) -> usize {
+        let v = self.entry(bucket).or_default();
+        *v += step;
+        *v
+    }
+
+    /// Get a bucket's count; missing buckets are not created.
+    fn get_bucket(&self, bucket: &T) -> usize {
+        match self.get(bucket) {
+            Some(&c) => c,
+            None => 0,
+        }
+    }
+
+    /// Set a bucket's count, removing it if the count is zero.
+    fn set_bucket(&mut self, bucket: T, count: usize) {
+        if count == 0 {
+            self.remove(&bucket);
+        } else {
+            self.insert(bucket, count);
+        }
+    }
+
+    fn total(&self) -> usize {
+        self.values().sum()
+    }
+
+    /// Nonzero buckets, in label order.
+    fn nonzero_buckets(&self) -> Vec<(T, usize)>
+    where
+        T: Clone,
+    {
+        self.iter()
+            .filter(|(_, &c)| c > 0)
+            .map(|(b, &c)| (b.clone(), c))
+            .collect()
+    }
+}
+
+impl<const N: usize> Histogram<usize> for [usize; N] {
+    /// Increment a bucket by the given amount. Panics if the bucket is out of bounds.
+    fn increment_bucket_by(&mut self, bucket: usize, step: usize) -> usize {
+        self[bucket] += step;
+        self[bucket]
+    }
+
+    /// Get a bucket's count; out-of-bounds buckets are always empty.
+    fn get_bucket(&self, &bucket: &usize) -> usize {
+        match self.get(bucket) {
+            Some(&c) => c,
+            None => 0,
+        }
+    }
+
+    /// Set a bucket's count. Panics if the bucket is out of bounds, unless the count is zero.
+    fn set_bucket(&mut self, bucket: usize, count: usize) {
+        if bucket < N || count > 0 {
+            self[bucket] = count;
+        }
+    }
+
+    fn total(&self) -> usize {
+        self.iter().sum()
+    }
+
+    /// Nonzero buckets, in label order.
+    fn nonzero_buckets(&self) -> Vec<(usize, usize)> {
+        self.iter()
+            .enumerate()
+            .filter(|(_, &c)| c > 0)
+            .map(|(b, &c)| (b, c))
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -143,6 +594,192 @@ mod test {
         assert_eq!(hist, vec![2, 2, 1, 1, 1, 1, 1, 1, 2, 2])
     }
 
+    #[test]
+    fn vec_analytics() {
+        let mut hist = Vec::new();
+        for &i in &[3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5] {
+            hist.increment_bucket(i);
+        }
+        assert_eq!(11, hist.total());
+        assert_eq!(
+            vec![(1, 2), (2, 1), (3, 2), (4, 1), (5, 3), (6, 1), (9, 1)],
+            hist.nonzero_buckets()
+        );
+        assert_eq!(Some((5, 3)), hist.max_bucket());
+        assert_eq!(vec![(5, 3), (1, 2), (3, 2)], hist.most_common(3));
+        assert_eq!(vec![(2, 1), (4, 1)], hist.least_common(2));
+        assert_eq!(hist.nonzero_buckets(), hist.sorted_by_label());
+        assert_eq!(7, hist.sorted_by_count().len());
+        assert_eq!(7, hist.most_common(100).len());
+        assert_eq!(None, Vec::new().max_bucket());
+        assert_eq!(0, Vec::new().total());
+    }
+
+    #[test]
+    fn map_analytics() {
+        let mut hist = HashMap::new();
+        for w in "the cat and the hat and the bat".split(' ') {
+            hist.increment_bucket(w);
+        }
+        // an explicitly empty bucket is ignored
+        hist.insert("dog", 0);
+        assert_eq!(8, hist.total());
+        assert_eq!(5, hist.nonzero_buckets().len());
+        assert_eq!(Some(("the", 3)), hist.max_bucket());
+        assert_eq!(
+            vec![("the", 3), ("and", 2), ("bat", 1), ("cat", 1), ("hat", 1)],
+            hist.sorted_by_count()
+        );
+        assert_eq!(
+            vec![("and", 2), ("bat", 1), ("cat", 1), ("hat", 1), ("the", 3)],
+            hist.sorted_by_label()
+        );
+        assert_eq!(vec![("bat", 1)], hist.least_common(1));
+        assert!(hist.most_common(0).is_empty());
+    }
+
+    #[test]
+    fn entropy() {
+        assert_eq!(Some(0.0), vec![0, 5].entropy());
+        assert_eq!(Some(1.0), vec![0, 2, 0, 2].entropy());
+        assert_eq!(Some(2.0), [1, 1, 1, 1].entropy());
+        assert_eq!(Some(1.0), "aabb".chars().histogram().entropy());
+        let words = vec!["red", "red", "red", "blue"];
+        let counts = words.iter().map(|w| w.to_string()).collect::<Counts<_>>();
+        assert_eq!(
+            Some(-(0.75f64 * 0.75f64.log2() + 0.25 * 0.25f64.log2())),
+            counts.entropy()
+        );
+        assert_eq!(None, Counts::<String>::new().entropy());
+        assert_eq!(None, vec![0, 0].entropy());
+    }
+
+    #[test]
+    fn collecting() {
+        let hist = vec![3, 1, 3, 3].into_iter().histogram();
+        assert_eq!(3, hist.get_bucket(&3));
+        assert_eq!(2, hist.len());
+        let counts = "abracadabra".chars().collect::<Counts<_>>();
+        assert_eq!(5, counts.get_bucket(&'a'));
+        assert_eq!(11, counts.total());
+        assert_eq!(&"abracadabra".chars().histogram(), counts.as_map());
+        assert_eq!(counts, "aaaaabbrrcd".chars().collect());
+        assert_eq!(Counts::<char>::default(), "".chars().collect());
+    }
+
+    #[test]
+    fn set_bucket() {
+        let mut hist = vec![1, 2];
+        hist.set_bucket(4, 7);
+        hist.set_bucket(0, 0);
+        hist.set_bucket(9, 0);
+        assert_eq!(vec![0, 2, 0, 0, 7], hist);
+        let mut hist = HashMap::new();
+        hist.set_bucket("a", 3);
+        hist.set_bucket("b", 0);
+        assert_eq!(1, hist.len());
+        hist.set_bucket("a", 0);
+        assert!(hist.is_empty());
+    }
+
+    #[test]
+    fn btree_map() {
+        let mut hist = BTreeMap::new();
+        for w in "one two three two three three".split(' ') {
+            hist.increment_bucket(w);
+        }
+        assert_eq!(6, hist.total());
+        // already in label order, with no sorting
+        assert_eq!(
+            vec![("one", 1), ("three", 3), ("two", 2)],
+            hist.nonzero_buckets()
+        );
+        assert_eq!(Some(("three", 3)), hist.max_bucket());
+        assert_eq!(1, hist.decrement_bucket_by("two", 1));
+        assert_eq!(0, hist.decrement_bucket("two"));
+        assert!(!hist.contains_key("two"));
+    }
+
+    #[test]
+    fn array() {
+        let mut hist = [0; 10];
+        for d in "31415926535".chars() {
+            hist.increment_bucket(d.to_digit(10).unwrap() as usize);
+        }
+        assert_eq!([0, 2, 1, 2, 1, 3, 1, 0, 0, 1], hist);
+        assert_eq!(0, hist.get_bucket(&99));
+        assert_eq!(vec![(5, 3), (1, 2), (3, 2)], hist.most_common(3));
+        hist.set_bucket(99, 0);
+        assert_eq!(3, hist.remove_bucket(5));
+        assert_eq!(8, hist.total());
+        let mut other = [0; 10];
+        other.merge(&hist);
+        assert_eq!(hist, other);
+    }
+
+    #[test]
+    #[should_panic]
+    fn array_out_of_bounds() {
+        [0; 3].increment_bucket(3);
+    }
+
+    #[test]
+    fn decrement_and_remove() {
+        let mut hist = vec![3, 1];
+        assert_eq!(2, hist.decrement_bucket(0));
+        assert_eq!(0, hist.decrement_bucket_by(0, 5));
+        assert_eq!(0, hist.decrement_bucket(1));
+        // saturates at zero, and doesn't create buckets
+        assert_eq!(0, hist.decrement_bucket(1));
+        assert_eq!(0, hist.decrement_bucket(7));
+        assert_eq!(vec![0, 0], hist);
+
+        let mut hist = "aabc".chars().collect::<Counts<_>>();
+        assert_eq!(2, hist.remove_bucket('a'));
+        assert_eq!(0, hist.remove_bucket('z'));
+        assert_eq!(1, hist.decrement_bucket_by('b', 0));
+        assert_eq!("bc".chars().collect::<Counts<_>>(), hist);
+    }
+
+    #[test]
+    fn algebra() {
+        let a = "aaabbc".chars().collect::<Counts<_>>();
+        let b = "abbbbdd".chars().collect::<Counts<_>>();
+
+        let mut sum = a.clone();
+        sum.merge(&b);
+        assert_eq!("aaaabbbbbbcdd".chars().collect::<Counts<_>>(), sum);
+
+        let mut diff = a.clone();
+        diff.subtract(&b);
+        assert_eq!("aac".chars().collect::<Counts<_>>(), diff);
+        // emptied buckets are removed, not left at zero
+        assert_eq!(2, diff.as_map().len());
+
+        let mut both = a.clone();
+        both.intersect(&b);
+        assert_eq!("abb".chars().collect::<Counts<_>>(), both);
+
+        let mut either = a.clone();
+        either.union(&b);
+        assert_eq!("aaabbbbcdd".chars().collect::<Counts<_>>(), either);
+
+        // across backends
+        let mut v = vec![0, 3, 1];
+        v.merge(
+            &vec![2, 0, 0, 4]
+                .into_iter()
+                .enumerate()
+                .fold(HashMap::new(), |mut h, (b, c)| {
+                    h.set_bucket(b, c);
+                    h
+                }),
+        );
+        assert_eq!(vec![2, 3, 1, 4], v);
+        v.subtract(&vec![5, 5]);
+        assert_eq!(vec![0, 0, 1, 4], v);
+    }
+
     #[test]
     fn int_map() {
         let mut hist = HashMap::new();
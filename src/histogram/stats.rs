@@ -0,0 +1,173 @@
+use crate::histogram::Histogram;
+use num_traits::ToPrimitive;
+
+/// I summarize a histogram as a distribution of values, where each bucket's label is the value
+/// (so for `Vec<usize>` it's the bucket's index) and its count is how many times the value occurs.
+/// Every statistic is `None` for an empty histogram. Labels which can't be represented as an `f64`
+/// are ignored.
+///
+/// # Examples
+///
+/// ```
+/// use aoc_2020::histogram::stats::Distribution;
+/// use aoc_2020::histogram::Histogram;
+///
+/// // live neighbor counts across a generation
+/// let mut hist = Vec::new();
+/// for n in vec![1, 2, 2, 3, 3, 3, 4, 8] {
+///     hist.increment_bucket(n);
+/// }
+/// assert_eq!(Some(3.25), hist.mean());
+/// assert_eq!(Some(3.0), hist.median());
+/// assert_eq!(Some(8.0), hist.percentile(100.0));
+/// assert_eq!(Some(3.9375), hist.variance());
+/// assert_eq!(None, Vec::new().mean());
+/// ```
+pub trait Distribution<T> {
+    fn mean(&self) -> Option<f64>;
+
+    /// The population variance: the mean squared distance from the mean.
+    fn variance(&self) -> Option<f64>;
+
+    fn std_dev(&self) -> Option<f64> {
+        self.variance().map(f64::sqrt)
+    }
+
+    /// The middle value, or the mean of the two middle values.
+    fn median(&self) -> Option<f64> {
+        self.percentile(50.0)
+    }
+
+    /// The value `p` percent of the way through the sorted values, interpolating linearly between
+    /// neighboring values. `None` if `p` isn't between zero and 100.
+    fn percentile(&self, p: f64) -> Option<f64>;
+}
+
+impl<T, H> Distribution<T> for H
+where
+    H: Histogram<T> + ?Sized,
+    T: ToPrimitive + Clone,
+{
+    fn mean(&self) -> Option<f64> {
+        let values = weighted_values(self);
+        let n = count(&values)?;
+        Some(values.iter().map(|&(v, c)| v * c as f64).sum::<f64>() / n)
+    }
+
+    fn variance(&self) -> Option<f64> {
+        let values = weighted_values(self);
+        let n = count(&values)?;
+        let mean = values.iter().map(|&(v, c)| v * c as f64).sum::<f64>() / n;
+        Some(
+            values
+                .iter()
+                .map(|&(v, c)| (v - mean).powi(2) * c as f64)
+                .sum::<f64>()
+                / n,
+        )
+    }
+
+    fn percentile(&self, p: f64) -> Option<f64> {
+        if !(0.0..=100.0).contains(&p) {
+            return None;
+        }
+        let values = weighted_values(self);
+        let n = count(&values)?;
+        let rank = p / 100.0 * (n - 1.0);
+        let (lo, hi) = (rank.floor() as usize, rank.ceil() as usize);
+        let (a, b) = (nth_value(&values, lo), nth_value(&values, hi));
+        Some(a + (b - a) * (rank - lo as f64))
+    }
+}
+
+/// The nonzero buckets as `(value, count)` pairs, ordered by value.
+fn weighted_values<T, H>(hist: &H) -> Vec<(f64, usize)>
+where
+    H: Histogram<T> + ?Sized,
+    T: ToPrimitive + Clone,
+{
+    let mut values = hist
+        .nonzero_buckets()
+        .into_iter()
+        .filter_map(|(b, c)| b.to_f64().map(|v| (v, c)))
+        .collect::<Vec<_>>();
+    values.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+    values
+}
+
+/// The total count, or `None` if it's zero.
+fn count(values: &[(f64, usize)]) -> Option<f64> {
+    match values.iter().map(|&(_, c)| c).sum::<usize>() {
+        0 => None,
+        n => Some(n as f64),
+    }
+}
+
+/// The `i`th smallest value, counting each value as many times as it occurs.
+fn nth_value(values: &[(f64, usize)], i: usize) -> f64 {
+    let mut remaining = i;
+    for &(v, c) in values {
+        if remaining < c {
+            return v;
+        }
+        remaining -= c;
+    }
+    panic!("Only {} values, so there's no value {}", i - remaining, i)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::histogram::{Counts, IntoHistogram};
+    use std::collections::{BTreeMap, HashMap};
+
+    fn approx(expected: f64, actual: Option<f64>) {
+        let actual = actual.unwrap();
+        assert!(
+            (expected - actual).abs() < 1e-9,
+            "expected {}, but got {}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn vec_by_index() {
+        let hist = vec![0, 2, 0, 2];
+        approx(2.0, hist.mean());
+        approx(1.0, hist.variance());
+        approx(1.0, hist.std_dev());
+        approx(2.0, hist.median());
+        approx(1.0, hist.percentile(0.0));
+        approx(1.0, hist.percentile(25.0));
+        approx(3.0, hist.percentile(100.0));
+        assert_eq!(None, hist.percentile(101.0));
+        assert_eq!(None, hist.percentile(f64::NAN));
+    }
+
+    #[test]
+    fn map_by_label() {
+        let hist = vec![-10, 20, 20, 30, 30, 30, 30].into_iter().histogram();
+        approx(150.0 / 7.0, hist.mean());
+        approx(30.0, hist.median());
+        approx(-1.0, hist.percentile(5.0));
+        approx(20.0, hist.percentile(100.0 / 3.0));
+        let counts = "aabb".chars().map(|c| c as u32).collect::<Counts<_>>();
+        approx(97.5, counts.median());
+        let mut ordered = BTreeMap::new();
+        ordered.increment_bucket_by(3u8, 1);
+        ordered.increment_bucket_by(5u8, 3);
+        approx(4.5, ordered.mean());
+        approx(0.75, ordered.variance());
+    }
+
+    #[test]
+    fn empty() {
+        let hist: HashMap<i32, usize> = HashMap::new();
+        assert_eq!(None, hist.mean());
+        assert_eq!(None, hist.variance());
+        assert_eq!(None, hist.std_dev());
+        assert_eq!(None, hist.median());
+        assert_eq!(None, vec![0, 0, 0].mean());
+    }
+}
@@ -1,6 +1,13 @@
+use std::borrow::Borrow;
 use std::collections::HashMap;
+use std::hash::Hash;
+use std::iter::FromIterator;
+use std::slice;
 
-/// I provide unique indexing of some `K`, useful for representing a set as an array.
+pub mod set;
+
+/// I provide unique indexing of some `K`, useful for representing a set as an array
+/// (see `set::IndexedSet`).
 ///
 /// # Examples:
 ///
@@ -29,3 +36,179 @@ impl Indexer<&str> for HashMap<String, usize> {
         }
     }
 }
+
+/// I assign each distinct key a dense index, in order of first appearance, and can map an index
+/// back to its key. That makes it easy to keep per-key data in a `Vec` (or bitset), and then turn
+/// it back into labelled output.
+///
+/// # Examples:
+///
+/// ```
+/// use aoc_2020::indexer::Interner;
+///
+/// let mut interner = Interner::new();
+/// let mut counts = vec![];
+/// for color in "red blue red green red".split(' ') {
+///     let idx = interner.index_of(color);
+///     if idx == counts.len() {
+///         counts.push(0);
+///     }
+///     counts[idx] += 1;
+/// }
+/// assert_eq!(Some(1), interner.get("blue"));
+/// assert_eq!(Some(&"green"), interner.key_of(2));
+/// assert_eq!(
+///     vec![(&"red", &3), (&"blue", &1), (&"green", &1)],
+///     interner.iter().zip(counts.iter()).collect::<Vec<_>>()
+/// );
+/// ```
+#[derive(Clone, Debug)]
+pub struct Interner<K> {
+    indices: HashMap<K, usize>,
+    keys: Vec<K>,
+}
+
+impl<K> Interner<K>
+where
+    K: Hash + Eq + Clone,
+{
+    pub fn new() -> Interner<K> {
+        Interner {
+            indices: HashMap::new(),
+            keys: Vec::new(),
+        }
+    }
+
+    /// The index of `key`, assigning it the next index if it's new.
+    pub fn index_of(&mut self, key: K) -> usize {
+        match self.indices.get(&key) {
+            Some(&idx) => idx,
+            None => {
+                let idx = self.keys.len();
+                self.keys.push(key.clone());
+                self.indices.insert(key, idx);
+                idx
+            }
+        }
+    }
+
+    /// The index of `key`, or `None` if it hasn't been assigned one.
+    pub fn get<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.indices.get(key).copied()
+    }
+
+    /// The key assigned index `idx`, or `None` if no key has been.
+    pub fn key_of(&self, idx: usize) -> Option<&K> {
+        self.keys.get(idx)
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Iterate over the keys in index order.
+    pub fn iter(&self) -> slice::Iter<'_, K> {
+        self.keys.iter()
+    }
+}
+
+impl<K> Default for Interner<K>
+where
+    K: Hash + Eq + Clone,
+{
+    fn default() -> Self {
+        Interner::new()
+    }
+}
+
+impl<K> Indexer<K> for Interner<K>
+where
+    K: Hash + Eq + Clone,
+{
+    fn index_of(&mut self, needle: K) -> usize {
+        Interner::index_of(self, needle)
+    }
+}
+
+impl<K> FromIterator<K> for Interner<K>
+where
+    K: Hash + Eq + Clone,
+{
+    fn from_iter<I: IntoIterator<Item = K>>(iter: I) -> Self {
+        let mut interner = Interner::new();
+        for k in iter {
+            interner.index_of(k);
+        }
+        interner
+    }
+}
+
+impl<'a, K> IntoIterator for &'a Interner<K>
+where
+    K: Hash + Eq + Clone,
+{
+    type Item = &'a K;
+    type IntoIter = slice::Iter<'a, K>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn hash_map_indexer() {
+        let mut indexer = HashMap::new();
+        assert_eq!(0, indexer.index_of("x"));
+        assert_eq!(1, indexer.index_of("y"));
+        assert_eq!(0, indexer.index_of("x"));
+    }
+
+    #[test]
+    fn interner_round_trip() {
+        let mut interner = Interner::new();
+        assert!(interner.is_empty());
+        assert_eq!(0, interner.index_of("shiny gold".to_string()));
+        assert_eq!(1, interner.index_of("dark red".to_string()));
+        assert_eq!(0, interner.index_of("shiny gold".to_string()));
+        assert_eq!(2, interner.len());
+        // look up by &str without allocating a String
+        assert_eq!(Some(1), interner.get("dark red"));
+        assert_eq!(None, interner.get("faded blue"));
+        assert_eq!(Some(&"dark red".to_string()), interner.key_of(1));
+        assert_eq!(None, interner.key_of(2));
+        for (i, k) in interner.iter().enumerate() {
+            assert_eq!(Some(i), interner.get(k));
+        }
+    }
+
+    #[test]
+    fn interner_generic() {
+        let interner = vec![(1, 2), (3, 4), (1, 2), (5, 6)]
+            .into_iter()
+            .collect::<Interner<_>>();
+        assert_eq!(3, interner.len());
+        assert_eq!(Some(2), interner.get(&(5, 6)));
+        assert_eq!(
+            vec![(1, 2), (3, 4), (5, 6)],
+            (&interner).into_iter().copied().collect::<Vec<_>>()
+        );
+
+        fn index_all<I: Indexer<char>>(indexer: &mut I, s: &str) -> Vec<usize> {
+            s.chars().map(|c| indexer.index_of(c)).collect()
+        }
+        let mut interner = Interner::default();
+        assert_eq!(vec![0, 1, 0, 2], index_all(&mut interner, "abac"));
+    }
+}
@@ -0,0 +1,282 @@
+use crate::indexer::{Indexer, Interner};
+use std::borrow::Borrow;
+use std::hash::Hash;
+use std::iter::FromIterator;
+
+const WORD_BITS: usize = 64;
+
+/// I am a set of indices, stored as a growable bitset, for keys assigned dense indices by an
+/// `Indexer`. Union, intersection, and difference work a word at a time, and an `Interner` can turn
+/// my members back into the original keys.
+///
+/// # Examples
+///
+/// ```
+/// use aoc_2020::indexer::set::IndexedSet;
+/// use aoc_2020::indexer::Interner;
+///
+/// let mut interner = Interner::new();
+/// let a = IndexedSet::from_keys(&mut interner, "abcx".chars());
+/// let b = IndexedSet::from_keys(&mut interner, "xyzb".chars());
+/// assert_eq!(6, a.union(&b).len());
+/// assert_eq!(
+///     vec![&'b', &'x'],
+///     a.intersection(&b).keys(&interner).collect::<Vec<_>>()
+/// );
+/// assert!(a.difference(&b).contains(&interner, &'c'));
+/// assert!(!a.difference(&b).contains(&interner, &'x'));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct IndexedSet {
+    words: Vec<u64>,
+}
+
+impl IndexedSet {
+    pub fn new() -> IndexedSet {
+        IndexedSet { words: Vec::new() }
+    }
+
+    /// Build a set of `keys`, indexing any new ones with `indexer`.
+    pub fn from_keys<K, I, T>(indexer: &mut I, keys: T) -> IndexedSet
+    where
+        I: Indexer<K>,
+        T: IntoIterator<Item = K>,
+    {
+        keys.into_iter().map(|k| indexer.index_of(k)).collect()
+    }
+
+    /// Add `key`, indexing it with `indexer` if it's new, returning whether it wasn't already
+    /// present.
+    pub fn insert<K, I>(&mut self, indexer: &mut I, key: K) -> bool
+    where
+        I: Indexer<K>,
+    {
+        self.insert_index(indexer.index_of(key))
+    }
+
+    /// Whether `key` is present. A key `interner` has never seen can't be.
+    pub fn contains<K, Q>(&self, interner: &Interner<K>, key: &Q) -> bool
+    where
+        K: Hash + Eq + Clone + Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        match interner.get(key) {
+            Some(idx) => self.contains_index(idx),
+            None => false,
+        }
+    }
+
+    /// Add an index, returning whether it wasn't already present.
+    pub fn insert_index(&mut self, idx: usize) -> bool {
+        let (w, bit) = (idx / WORD_BITS, 1 << (idx % WORD_BITS));
+        if w >= self.words.len() {
+            self.words.resize(w + 1, 0);
+        }
+        let absent = self.words[w] & bit == 0;
+        self.words[w] |= bit;
+        absent
+    }
+
+    /// Remove an index, returning whether it was present.
+    pub fn remove_index(&mut self, idx: usize) -> bool {
+        let present = self.contains_index(idx);
+        if present {
+            self.words[idx / WORD_BITS] &= !(1 << (idx % WORD_BITS));
+        }
+        present
+    }
+
+    pub fn contains_index(&self, idx: usize) -> bool {
+        match self.words.get(idx / WORD_BITS) {
+            Some(w) => w & (1 << (idx % WORD_BITS)) != 0,
+            None => false,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    pub fn union(&self, other: &IndexedSet) -> IndexedSet {
+        let (long, short) = if self.words.len() >= other.words.len() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        let mut words = long.words.clone();
+        for (w, o) in words.iter_mut().zip(short.words.iter()) {
+            *w |= o;
+        }
+        IndexedSet { words }
+    }
+
+    pub fn intersection(&self, other: &IndexedSet) -> IndexedSet {
+        IndexedSet {
+            words: self
+                .words
+                .iter()
+                .zip(other.words.iter())
+                .map(|(w, o)| w & o)
+                .collect(),
+        }
+    }
+
+    /// The members of mine which aren't in `other`.
+    pub fn difference(&self, other: &IndexedSet) -> IndexedSet {
+        let mut words = self.words.clone();
+        for (w, o) in words.iter_mut().zip(other.words.iter()) {
+            *w &= !o;
+        }
+        IndexedSet { words }
+    }
+
+    /// Iterate over my indices in ascending order.
+    pub fn indices(&self) -> Indices<'_> {
+        Indices {
+            words: &self.words,
+            word: 0,
+            remaining: self.words.first().copied().unwrap_or(0),
+        }
+    }
+
+    /// Iterate over my members' keys, in index order.
+    pub fn keys<'a, K>(&'a self, interner: &'a Interner<K>) -> impl Iterator<Item = &'a K> + 'a
+    where
+        K: Hash + Eq + Clone,
+    {
+        self.indices().map(move |idx| {
+            interner
+                .key_of(idx)
+                .expect("Index wasn't assigned by this interner")
+        })
+    }
+}
+
+/// Sets are equal if they've the same members, regardless of how much space they've grown.
+impl PartialEq for IndexedSet {
+    fn eq(&self, other: &Self) -> bool {
+        let (long, short) = if self.words.len() >= other.words.len() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        long.words[..short.words.len()] == short.words[..]
+            && long.words[short.words.len()..].iter().all(|&w| w == 0)
+    }
+}
+
+impl Eq for IndexedSet {}
+
+impl FromIterator<usize> for IndexedSet {
+    fn from_iter<T: IntoIterator<Item = usize>>(iter: T) -> Self {
+        let mut set = IndexedSet::new();
+        for idx in iter {
+            set.insert_index(idx);
+        }
+        set
+    }
+}
+
+pub struct Indices<'a> {
+    words: &'a [u64],
+    word: usize,
+    remaining: u64,
+}
+
+impl<'a> Iterator for Indices<'a> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.remaining == 0 {
+            self.word += 1;
+            self.remaining = *self.words.get(self.word)?;
+        }
+        let bit = self.remaining.trailing_zeros() as usize;
+        // clear the lowest set bit
+        self.remaining &= self.remaining - 1;
+        Some(self.word * WORD_BITS + bit)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn indices() {
+        let mut s = IndexedSet::new();
+        assert!(s.is_empty());
+        assert!(s.insert_index(3));
+        assert!(!s.insert_index(3));
+        assert!(s.insert_index(200));
+        assert!(s.insert_index(64));
+        assert!(s.insert_index(0));
+        assert_eq!(4, s.len());
+        assert!(s.contains_index(64));
+        assert!(!s.contains_index(65));
+        assert!(!s.contains_index(10_000));
+        assert_eq!(vec![0, 3, 64, 200], s.indices().collect::<Vec<_>>());
+        assert!(s.remove_index(200));
+        assert!(!s.remove_index(200));
+        assert!(!s.remove_index(10_000));
+        assert_eq!(vec![0, 3, 64], s.indices().collect::<Vec<_>>());
+        assert_eq!(s, vec![64, 3, 0].into_iter().collect());
+    }
+
+    #[test]
+    fn algebra() {
+        let a = (0..100).filter(|n| n % 2 == 0).collect::<IndexedSet>();
+        let b = (0..150).filter(|n| n % 3 == 0).collect::<IndexedSet>();
+        assert_eq!(
+            (0..150)
+                .filter(|n| n % 3 == 0 || (n % 2 == 0 && *n < 100))
+                .collect::<IndexedSet>(),
+            a.union(&b)
+        );
+        assert_eq!(a.union(&b), b.union(&a));
+        assert_eq!(
+            (0..100).filter(|n| n % 6 == 0).collect::<IndexedSet>(),
+            a.intersection(&b)
+        );
+        assert_eq!(a.intersection(&b), b.intersection(&a));
+        assert_eq!(
+            (0..100)
+                .filter(|n| n % 2 == 0 && n % 3 != 0)
+                .collect::<IndexedSet>(),
+            a.difference(&b)
+        );
+        assert!(a.difference(&a).is_empty());
+        // equality ignores trailing empty words
+        assert_eq!(IndexedSet::new(), a.difference(&a));
+    }
+
+    #[test]
+    fn customs_groups() {
+        let input = "abc\n\na\nb\nc\n\nab\nac\n\na\na\na\na\n\nb";
+        let mut interner = Interner::new();
+        let (mut anyone, mut everyone) = (0, 0);
+        for group in input.split("\n\n") {
+            let sets = group
+                .lines()
+                .map(|l| IndexedSet::from_keys(&mut interner, l.chars()))
+                .collect::<Vec<_>>();
+            anyone += sets.iter().fold(IndexedSet::new(), |a, s| a.union(s)).len();
+            everyone += sets[1..]
+                .iter()
+                .fold(sets[0].clone(), |a, s| a.intersection(s))
+                .len();
+        }
+        assert_eq!(11, anyone);
+        assert_eq!(6, everyone);
+        let mut s = IndexedSet::new();
+        assert!(s.insert(&mut interner, 'q'));
+        assert!(s.contains(&interner, &'q'));
+        assert!(!s.contains(&interner, &'a'));
+        assert!(!s.contains(&interner, &'z'));
+        assert_eq!(vec![&'q'], s.keys(&interner).collect::<Vec<_>>());
+    }
+}
@@ -21,13 +21,23 @@ pub mod find_pairs;
 #[macro_use]
 pub mod geom;
 pub mod geom2d;
+pub mod graph;
 pub mod histogram;
 pub mod indexer;
+pub mod navigation;
+pub mod normalize;
 pub mod passport;
 pub mod password;
+pub mod random;
 
+/// I read `input.txt`, normalizing it (and reporting any changes made) before trimming it.
 pub fn read_input() -> String {
-    fs::read_to_string("input.txt").unwrap().trim().to_string()
+    let n = normalize::normalize(&fs::read_to_string("input.txt").unwrap());
+    if n.is_changed() {
+        let warning = console::Style::new().yellow();
+        println!("{:>12} {}", warning.apply_to("Normalized"), n);
+    }
+    n.text.trim().to_string()
 }
 
 #[deprecated]
@@ -40,7 +50,8 @@ where
 
 /// I convert a multi-line `&str` into a `Vec<String>` by splitting on "paragraph breaks" which are
 /// defined as two sequential newline characters. Each paragraph is further "unwrapped" by replacing
-/// all internal newlines w/ a single space.
+/// all internal newlines w/ a single space. The input is normalized first, so CRLF line endings and
+/// trailing whitespace don't interfere with finding paragraph breaks.
 ///
 /// # Examples
 ///
@@ -57,7 +68,11 @@ where
 /// ])
 /// ```
 pub fn unwrap_paragraphs(input: &str) -> Vec<String> {
-    input.split("\n\n").map(|s| s.replace('\n', " ")).collect()
+    normalize::normalize(input)
+        .text
+        .split("\n\n")
+        .map(|s| s.replace('\n', " "))
+        .collect()
 }
 
 pub fn with_duration<T, F>(f: F) -> (T, Duration)
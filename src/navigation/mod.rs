@@ -0,0 +1,227 @@
+use crate::geom::point::Point;
+use crate::geom2d::{Dir, Turn};
+use std::convert::TryFrom;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// I am a single navigation instruction: an action letter followed by a number, like `N3`, `F10`,
+/// or `R90`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Instruction {
+    /// Move (or move the waypoint) in an absolute direction, regardless of heading.
+    Move(Dir, usize),
+    /// Turn the heading (or rotate the waypoint about the ship).
+    Turn(Turn),
+    /// Move along the heading (or toward the waypoint, repeatedly).
+    Forward(usize),
+}
+
+impl FromStr for Instruction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        match s.chars().next() {
+            Some('L') | Some('R') => Ok(Instruction::Turn(s.parse()?)),
+            Some(c) => {
+                let n = s[c.len_utf8()..]
+                    .parse()
+                    .map_err(|e| format!("Unparseable number in '{}': {}", s, e))?;
+                if c == 'F' {
+                    Ok(Instruction::Forward(n))
+                } else {
+                    Ok(Instruction::Move(Dir::try_from(c)?, n))
+                }
+            }
+            None => Err("Empty instruction".to_string()),
+        }
+    }
+}
+
+/// I parse a stream of instructions, one per line.
+pub fn parse_instructions(input: &str) -> Result<Vec<Instruction>, String> {
+    input
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| l.parse())
+        .collect()
+}
+
+/// I am a ship (or turtle) on the plane, with a position and a heading. If I've a waypoint, it's
+/// relative to my position, and it's what movement and turning instructions affect; `Forward`
+/// then moves me toward it. Like the rest of `geom2d`, North is toward negative y.
+///
+/// # Examples
+///
+/// ```
+/// use aoc_2020::geom::point::Point;
+/// use aoc_2020::geom2d::Dir;
+/// use aoc_2020::navigation::{parse_instructions, Ship};
+///
+/// let instructions = parse_instructions("F10\nN3\nF7\nR90\nF11").unwrap();
+/// let mut ship = Ship::new(Dir::East);
+/// ship.execute_all(&instructions);
+/// assert_eq!(Point::new([17, 8]), ship.position());
+/// assert_eq!(Dir::South, ship.heading());
+/// assert_eq!(25, ship.manhattan_distance());
+///
+/// let mut ship = Ship::with_waypoint(Point::new([10, -1]));
+/// ship.execute_all(&instructions);
+/// assert_eq!(286, ship.manhattan_distance());
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Ship {
+    start: Point<2>,
+    position: Point<2>,
+    heading: Dir,
+    waypoint: Option<Point<2>>,
+    odometer: usize,
+}
+
+impl Ship {
+    /// A ship at the origin, with the given heading and no waypoint.
+    pub fn new(heading: Dir) -> Ship {
+        Ship {
+            start: Point::origin(),
+            position: Point::origin(),
+            heading,
+            waypoint: None,
+            odometer: 0,
+        }
+    }
+
+    /// A ship at the origin, heading East, steering by a waypoint at the given offset.
+    pub fn with_waypoint(waypoint: Point<2>) -> Ship {
+        Ship {
+            waypoint: Some(waypoint),
+            ..Ship::new(Dir::East)
+        }
+    }
+
+    pub fn position(&self) -> Point<2> {
+        self.position
+    }
+
+    pub fn heading(&self) -> Dir {
+        self.heading
+    }
+
+    /// The waypoint's offset from my position, if I've one.
+    pub fn waypoint(&self) -> Option<Point<2>> {
+        self.waypoint
+    }
+
+    pub fn execute(&mut self, instruction: &Instruction) {
+        match (instruction, self.waypoint.as_mut()) {
+            (Instruction::Move(d, n), Some(w)) => *w += d.delta() * *n as isize,
+            (Instruction::Move(d, n), None) => self.travel(d.delta() * *n as isize),
+            (Instruction::Turn(t), Some(w)) => *w = t.transform().apply(w),
+            (Instruction::Turn(t), None) => self.heading = self.heading.turn(t.quarter_turns()),
+            (Instruction::Forward(n), Some(w)) => {
+                let delta = *w * *n as isize;
+                self.travel(delta)
+            }
+            (Instruction::Forward(n), None) => self.travel(self.heading.delta() * *n as isize),
+        }
+    }
+
+    pub fn execute_all<'a, I>(&mut self, instructions: I)
+    where
+        I: IntoIterator<Item = &'a Instruction>,
+    {
+        for i in instructions {
+            self.execute(i);
+        }
+    }
+
+    fn travel(&mut self, delta: Point<2>) {
+        self.position += delta;
+        self.odometer += delta.manhattan_distance(&Point::origin());
+    }
+
+    /// The Manhattan distance between my starting position and where I am now.
+    pub fn manhattan_distance(&self) -> usize {
+        self.position.manhattan_distance(&self.start)
+    }
+
+    /// The total Manhattan distance I've moved, including any doubling back.
+    pub fn odometer(&self) -> usize {
+        self.odometer
+    }
+}
+
+impl Display for Ship {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} heading {:?}", self.position, self.heading)?;
+        if let Some(w) = self.waypoint {
+            write!(f, " with waypoint {}", w)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE: &str = "F10
+N3
+F7
+R90
+F11";
+
+    #[test]
+    fn parse() {
+        use Instruction::*;
+        assert_eq!(
+            vec![
+                Forward(10),
+                Move(Dir::North, 3),
+                Forward(7),
+                Turn("R90".parse().unwrap()),
+                Forward(11)
+            ],
+            parse_instructions(EXAMPLE).unwrap()
+        );
+        assert_eq!(Ok(Move(Dir::West, 5)), "W5".parse());
+        assert!("R45".parse::<Instruction>().is_err());
+        assert!("X3".parse::<Instruction>().is_err());
+        assert!("N".parse::<Instruction>().is_err());
+        assert!(parse_instructions("N3\nQ1").is_err());
+    }
+
+    #[test]
+    fn heading() {
+        let mut ship = Ship::new(Dir::East);
+        ship.execute_all(&parse_instructions(EXAMPLE).unwrap());
+        assert_eq!(Point::new([17, 8]), ship.position());
+        assert_eq!(25, ship.manhattan_distance());
+        assert_eq!(31, ship.odometer());
+        ship.execute_all(&parse_instructions("L180\nF8\nS8").unwrap());
+        assert_eq!(Dir::North, ship.heading());
+        assert_eq!(Point::new([17, 8]), ship.position());
+        assert_eq!(47, ship.odometer());
+        assert_eq!("(17,8) heading North", ship.to_string());
+    }
+
+    #[test]
+    fn waypoint() {
+        let mut ship = Ship::with_waypoint(Point::new([10, -1]));
+        let instructions = parse_instructions(EXAMPLE).unwrap();
+        ship.execute_all(&instructions[..3]);
+        assert_eq!(Point::new([170, -38]), ship.position());
+        assert_eq!(Some(Point::new([10, -4])), ship.waypoint());
+        ship.execute_all(&instructions[3..]);
+        assert_eq!(Some(Point::new([4, 10])), ship.waypoint());
+        assert_eq!(Point::new([214, 72]), ship.position());
+        assert_eq!(286, ship.manhattan_distance());
+        assert_eq!(Dir::East, ship.heading());
+        ship.execute(&"L270".parse().unwrap());
+        assert_eq!(Some(Point::new([-10, 4])), ship.waypoint());
+        assert_eq!(
+            "(214,72) heading East with waypoint (-10,4)",
+            ship.to_string()
+        );
+    }
+}
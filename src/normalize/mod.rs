@@ -0,0 +1,132 @@
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+/// I am the result of normalizing some input text: the cleaned-up text, along with a report of what
+/// had to be changed to get there.
+#[derive(Debug, Eq, PartialEq)]
+pub struct Normalized {
+    pub text: String,
+    /// Whether a leading byte order mark was removed.
+    pub bom_stripped: bool,
+    /// How many `\r\n` or bare `\r` line endings were replaced with `\n`.
+    pub line_endings_fixed: usize,
+    /// How many lines had trailing whitespace removed.
+    pub lines_trimmed: usize,
+}
+
+impl Normalized {
+    pub fn is_changed(&self) -> bool {
+        self.bom_stripped || self.line_endings_fixed > 0 || self.lines_trimmed > 0
+    }
+}
+
+impl Display for Normalized {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if !self.is_changed() {
+            return write!(f, "no changes");
+        }
+        let mut changes = Vec::new();
+        if self.bom_stripped {
+            changes.push(String::from("stripped BOM"));
+        }
+        if self.line_endings_fixed > 0 {
+            changes.push(format!("fixed {} line endings", self.line_endings_fixed));
+        }
+        if self.lines_trimmed > 0 {
+            changes.push(format!("trimmed {} lines", self.lines_trimmed));
+        }
+        write!(f, "{}", changes.join(", "))
+    }
+}
+
+/// I normalize input text so loaders only ever have to deal with `\n`-terminated lines: a leading
+/// BOM is stripped, `\r\n` and bare `\r` line endings become `\n`, and trailing whitespace is
+/// removed from each line. Leading whitespace and blank lines are left alone, as they can be
+/// significant (e.g., paragraph breaks).
+///
+/// # Examples
+///
+/// ```
+/// use aoc_2020::normalize::normalize;
+///
+/// let n = normalize("\u{feff}abc \r\n\r\ndef\r\n");
+/// assert_eq!("abc\n\ndef\n", n.text);
+/// assert!(n.bom_stripped);
+/// assert_eq!(3, n.line_endings_fixed);
+/// assert_eq!(1, n.lines_trimmed);
+/// assert_eq!("stripped BOM, fixed 3 line endings, trimmed 1 lines", n.to_string());
+/// ```
+pub fn normalize(input: &str) -> Normalized {
+    let (input, bom_stripped) = match input.strip_prefix('\u{feff}') {
+        Some(rest) => (rest, true),
+        None => (input, false),
+    };
+    let mut text = String::with_capacity(input.len());
+    let mut line_endings_fixed = 0;
+    let mut lines_trimmed = 0;
+    let mut rest = input;
+    while !rest.is_empty() {
+        let (line, ending, next) = match rest.find(&['\n', '\r'][..]) {
+            Some(i) if rest[i..].starts_with("\r\n") => (&rest[..i], "\r\n", &rest[(i + 2)..]),
+            Some(i) => (&rest[..i], &rest[i..=i], &rest[(i + 1)..]),
+            None => (rest, "", ""),
+        };
+        let trimmed = line.trim_end();
+        if trimmed.len() != line.len() {
+            lines_trimmed += 1;
+        }
+        text.push_str(trimmed);
+        if !ending.is_empty() {
+            if ending != "\n" {
+                line_endings_fixed += 1;
+            }
+            text.push('\n');
+        }
+        rest = next;
+    }
+    Normalized {
+        text,
+        bom_stripped,
+        line_endings_fixed,
+        lines_trimmed,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn clean_input_is_unchanged() {
+        let s = "abc\n\n  def\nghi";
+        let n = normalize(s);
+        assert_eq!(s, n.text);
+        assert!(!n.is_changed());
+        assert_eq!("no changes", n.to_string());
+    }
+
+    #[test]
+    fn line_endings() {
+        let n = normalize("a\r\nb\rc\n\r\nd");
+        assert_eq!("a\nb\nc\n\nd", n.text);
+        assert_eq!(3, n.line_endings_fixed);
+        assert_eq!(0, n.lines_trimmed);
+        assert!(!n.bom_stripped);
+    }
+
+    #[test]
+    fn trailing_whitespace() {
+        let n = normalize("a \t\n b\n\t\nc  ");
+        assert_eq!("a\n b\n\nc", n.text);
+        assert_eq!(3, n.lines_trimmed);
+        assert_eq!(0, n.line_endings_fixed);
+    }
+
+    #[test]
+    fn paragraphs_survive() {
+        assert_eq!(
+            vec!["a b c", "d"],
+            crate::unwrap_paragraphs("\u{feff}a b \r\nc\r\n\r\nd")
+        );
+    }
+}
@@ -1,10 +1,13 @@
+use crate::random::Rng;
 use regex::Regex;
 use std::collections::HashMap;
+use std::fmt;
+use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
 
-#[derive(Hash, Eq, PartialEq, Debug, EnumIter)]
+#[derive(Hash, Eq, PartialEq, Debug, Copy, Clone, EnumIter)]
 pub enum Field {
     BirthYear,
     IssueYear,
@@ -58,6 +61,21 @@ impl Field {
     }
 }
 
+impl Display for Field {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Field::BirthYear => "byr",
+            Field::IssueYear => "iyr",
+            Field::ExpirationYear => "eyr",
+            Field::Height => "hgt",
+            Field::HairColor => "hcl",
+            Field::EyeColor => "ecl",
+            Field::PassportID => "pid",
+            Field::CountryID => "cid",
+        })
+    }
+}
+
 impl FromStr for Field {
     type Err = String;
 
@@ -76,6 +94,86 @@ impl FromStr for Field {
     }
 }
 
+const EYE_COLORS: [&str; 7] = ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"];
+const HEX_DIGITS: &[u8] = b"0123456789abcdef";
+
+impl Field {
+    fn random_value(&self, rng: &mut Rng, valid: bool) -> String {
+        match (self, valid) {
+            (Field::BirthYear, true) => rng.between(1920, 2002).to_string(),
+            (Field::BirthYear, false) => rng.between(1850, 1919).to_string(),
+            (Field::IssueYear, true) => rng.between(2010, 2020).to_string(),
+            (Field::IssueYear, false) => rng.between(2021, 2040).to_string(),
+            (Field::ExpirationYear, true) => rng.between(2020, 2030).to_string(),
+            (Field::ExpirationYear, false) => rng.between(1990, 2019).to_string(),
+            (Field::Height, true) => match rng.chance(0.5) {
+                true => format!("{}cm", rng.between(150, 193)),
+                false => format!("{}in", rng.between(59, 76)),
+            },
+            (Field::Height, false) => match rng.below(3) {
+                0 => format!("{}cm", rng.between(194, 250)),
+                1 => format!("{}in", rng.between(20, 58)),
+                _ => rng.between(150, 193).to_string(),
+            },
+            (Field::HairColor, true) => (0..6).fold(String::from("#"), |mut s, _| {
+                s.push(*rng.pick(HEX_DIGITS) as char);
+                s
+            }),
+            (Field::HairColor, false) => (0..6).fold(String::new(), |mut s, _| {
+                s.push(*rng.pick(HEX_DIGITS) as char);
+                s
+            }),
+            (Field::EyeColor, true) => rng.pick(&EYE_COLORS).to_string(),
+            (Field::EyeColor, false) => rng.pick(&["xry", "zzz", "red"]).to_string(),
+            (Field::PassportID, true) => format!("{:09}", rng.below(1_000_000_000)),
+            (Field::PassportID, false) => format!("{:010}", rng.below(1_000_000_000)),
+            (Field::CountryID, _) => rng.between(100, 350).to_string(),
+        }
+    }
+}
+
+/// I generate a single random passport, with every field valid except those in `invalid`, which
+/// are either omitted or given a bad value. `CountryID` is optional and can't be invalid, so it's
+/// randomly included or not. Fields are separated by spaces or newlines, like the real input.
+pub fn random_passport(rng: &mut Rng, invalid: &[Field]) -> String {
+    let mut parts = Vec::new();
+    for f in Field::iter() {
+        let valid = !invalid.contains(&f);
+        if (f == Field::CountryID || !valid) && rng.chance(0.5) {
+            continue;
+        }
+        parts.push(format!("{}:{}", f, f.random_value(rng, valid)));
+    }
+    rng.shuffle(&mut parts);
+    let mut s = String::new();
+    for (i, p) in parts.iter().enumerate() {
+        if i > 0 {
+            s.push(if rng.chance(0.25) { '\n' } else { ' ' });
+        }
+        s.push_str(p);
+    }
+    s
+}
+
+/// I generate `count` random passports, each of which has one of its required fields invalidated
+/// with probability `invalid_rate`.
+pub fn random_input(rng: &mut Rng, count: usize, invalid_rate: f64) -> String {
+    let required = Field::iter()
+        .filter(|f| *f != Field::CountryID)
+        .collect::<Vec<_>>();
+    (0..count)
+        .map(|_| {
+            if rng.chance(invalid_rate) {
+                let f = required[rng.below(required.len())];
+                random_passport(rng, &[f])
+            } else {
+                random_passport(rng, &[])
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
 pub struct Passport {
     fields: HashMap<Field, String>,
 }
@@ -206,4 +304,38 @@ iyr:2011 ecl:brn hgt:59in
                 .count()
         );
     }
+
+    #[test]
+    fn test_random_passport() {
+        let mut rng = aoc::random::Rng::new(4);
+        for _ in 0..100 {
+            let pp = aoc::unwrap_paragraphs(&random_passport(&mut rng, &[]))[0]
+                .parse::<Passport>()
+                .unwrap();
+            assert!(pp.is_valid());
+            for f in Field::iter().filter(|&f| f != Field::CountryID) {
+                let pp = aoc::unwrap_paragraphs(&random_passport(&mut rng, &[f]))[0]
+                    .parse::<Passport>()
+                    .unwrap();
+                assert!(!pp.is_valid(), "{:?} should be invalid", f);
+            }
+        }
+    }
+
+    #[test]
+    fn test_random_input() {
+        let mut rng = aoc::random::Rng::new(4);
+        let parse = |s: &str| {
+            aoc::unwrap_paragraphs(s)
+                .iter()
+                .map(|s| s.parse::<Passport>().unwrap())
+                .collect::<Vec<_>>()
+        };
+        let all_valid = parse(&random_input(&mut rng, 50, 0.0));
+        assert_eq!(50, all_valid.len());
+        assert!(all_valid.iter().all(|p| p.is_valid()));
+        let all_invalid = parse(&random_input(&mut rng, 50, 1.0));
+        assert_eq!(50, all_invalid.len());
+        assert!(all_invalid.iter().all(|p| !p.is_valid()));
+    }
 }
@@ -0,0 +1,125 @@
+/// I am a small, deterministic pseudo-random number generator (SplitMix64), suitable for building
+/// reproducible puzzle inputs for stress tests, fuzzing, and benchmarks. I am _not_ suitable for
+/// anything cryptographic.
+///
+/// # Examples
+///
+/// ```
+/// use aoc_2020::random::Rng;
+///
+/// let mut a = Rng::new(42);
+/// let mut b = Rng::new(42);
+/// assert_eq!(a.next_u64(), b.next_u64());
+/// let n = a.below(10);
+/// assert!(n < 10);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Generate a uniformly distributed number in `0..n`, which must be non-empty.
+    pub fn below(&mut self, n: usize) -> usize {
+        self.below_u64(n as u64) as usize
+    }
+
+    fn below_u64(&mut self, n: u64) -> u64 {
+        assert!(n > 0, "Can't pick from an empty range");
+        // reject the top sliver so the modulus doesn't bias toward small values
+        let zone = u64::MAX - u64::MAX % n;
+        loop {
+            let r = self.next_u64();
+            if r < zone {
+                return r % n;
+            }
+        }
+    }
+
+    /// Generate a uniformly distributed number in `lo..=hi`.
+    pub fn between(&mut self, lo: i64, hi: i64) -> i64 {
+        assert!(lo <= hi, "Empty range {}..={}", lo, hi);
+        // `hi - lo` may not fit in an i64, but it always fits in a u64
+        match hi.wrapping_sub(lo) as u64 {
+            u64::MAX => self.next_u64() as i64,
+            span => lo.wrapping_add(self.below_u64(span + 1) as i64),
+        }
+    }
+
+    /// Flip a coin which comes up `true` with probability `p`.
+    pub fn chance(&mut self, p: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < p
+    }
+
+    /// Pick a random element of the passed non-empty slice.
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    /// Shuffle the passed slice in place (Fisher-Yates).
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn deterministic() {
+        let a = (0..10).fold(Rng::new(7), |mut r, _| {
+            r.next_u64();
+            r
+        });
+        let b = (0..10).fold(Rng::new(7), |mut r, _| {
+            r.next_u64();
+            r
+        });
+        assert_eq!(a.state, b.state);
+        assert_ne!(Rng::new(7).next_u64(), Rng::new(8).next_u64());
+    }
+
+    #[test]
+    fn bounded() {
+        let mut rng = Rng::new(1);
+        let mut seen = [false; 6];
+        for _ in 0..1000 {
+            let n = rng.between(-2, 3);
+            assert!((-2..=3).contains(&n));
+            seen[(n + 2) as usize] = true;
+        }
+        assert!(seen.iter().all(|&s| s));
+        // spans too wide for an i64
+        let (lo, hi) = (i64::MIN, i64::MIN + 1);
+        assert!((lo..=hi).contains(&rng.between(lo, hi)));
+        let n = rng.between(-1, i64::MAX);
+        assert!(n >= -1);
+        rng.between(i64::MIN, i64::MAX);
+        assert_eq!(i64::MAX, rng.between(i64::MAX, i64::MAX));
+    }
+
+    #[test]
+    fn shuffle_is_a_permutation() {
+        let mut rng = Rng::new(99);
+        let mut items = (0..50).collect::<Vec<_>>();
+        rng.shuffle(&mut items);
+        assert_ne!((0..50).collect::<Vec<_>>(), items);
+        items.sort();
+        assert_eq!((0..50).collect::<Vec<_>>(), items);
+    }
+}